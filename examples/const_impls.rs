@@ -0,0 +1,57 @@
+//! `const` trait impls, requires a nightly compiler:
+//!
+//! ```text
+//! cargo +nightly run --example const_impls --features nightly
+//! ```
+
+#![feature(const_trait_impl, const_convert, const_default)]
+
+use implem::implem;
+
+pub struct MyType(u32);
+implem! {
+    for MyType {
+        const From<u32> {
+            |n| Self(n)
+        }
+        const Default {
+            || Self(7)
+        }
+        const Deref<Target = u32> {
+            |&self| &self.0,
+            |&mut self| &mut self.0,
+        }
+    }
+}
+
+pub struct Wrapper(u32);
+implem! {
+    for Wrapper {
+        const Into<u32> {
+            |self| self.0
+        }
+    }
+}
+
+const FOO: MyType = MyType::from(42u32);
+const DEFAULT: MyType = MyType::default();
+const DEREF: u32 = *MyType(3);
+const INTO: u32 = Wrapper(11).into();
+
+fn main() {
+    println!("FOO: {}", FOO.0);
+    assert_eq!(FOO.0, 42);
+    println!("DEFAULT: {}", DEFAULT.0);
+    assert_eq!(DEFAULT.0, 7);
+    println!("DEREF: {}", DEREF);
+    assert_eq!(DEREF, 3);
+    println!("INTO: {}", INTO);
+    assert_eq!(INTO, 11);
+
+    let mut val = MyType::default();
+    *val += 1;
+    assert_eq!(*val, 8);
+
+    println!();
+    println!("done");
+}
@@ -1,3 +1,5 @@
+#![deny(clippy::all)]
+
 use implem::implem;
 
 pub struct MyStruct {
@@ -0,0 +1,64 @@
+//! `Fn`-family impls, requires a nightly compiler:
+//!
+//! ```text
+//! cargo +nightly run --example fn_traits --features nightly
+//! ```
+
+#![feature(fn_traits, unboxed_closures)]
+
+use implem::implem;
+
+pub struct Adder {
+    offset: i32,
+}
+implem! {
+    for Adder {
+        FnOnce<(i32, i32), Output = i32> {
+            |self, (a, b)| self.offset + a + b
+        }
+        FnMut<(i32, i32)> {
+            |&mut self, (a, b)| self.offset + a + b
+        }
+        Fn<(i32, i32)> {
+            |&self, (a, b)| self.offset + a + b
+        }
+    }
+}
+
+pub struct Counter {
+    count: usize,
+}
+implem! {
+    for Counter {
+        FnOnce<(), Output = usize> {
+            |self, ()| self.count + 1
+        }
+        FnMut<()> {
+            |&mut self, ()| {
+                self.count += 1;
+                self.count
+            }
+        }
+    }
+}
+
+fn call_twice(mut f: impl FnMut() -> usize) -> usize {
+    f();
+    f()
+}
+
+fn main() {
+    let adder = Adder { offset: 10 };
+    println!("adder(1, 2): {}", adder(1, 2));
+    assert_eq!(adder(1, 2), 13);
+    let sums: Vec<i32> = [(1, 1), (2, 3)].iter().map(|&(a, b)| adder(a, b)).collect();
+    assert_eq!(sums, vec![12, 15]);
+
+    let mut counter = Counter { count: 0 };
+    assert_eq!(counter(), 1);
+    assert_eq!(call_twice(&mut counter), 3);
+    println!("counter: {}", counter.count);
+
+    println!();
+    println!("done");
+}
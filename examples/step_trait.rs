@@ -0,0 +1,42 @@
+//! `Step` impl making ranges of a newtype iterable, requires a nightly compiler:
+//!
+//! ```text
+//! cargo +nightly run --example step_trait --features nightly
+//! ```
+
+#![feature(step_trait)]
+
+use std::iter::Step;
+
+use implem::implem;
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct UserId(u64);
+implem! {
+    for UserId {
+        Step {
+            steps_between: |start, end| u64::steps_between(&start.0, &end.0),
+            forward_checked: |start, count| u64::forward_checked(start.0, count).map(UserId),
+            backward_checked: |start, count| u64::backward_checked(start.0, count).map(UserId),
+        }
+    }
+}
+
+fn main() {
+    let ids = (UserId(0)..UserId(5)).collect::<Vec<_>>();
+    println!("ids: {:?}", ids);
+    assert_eq!(
+        ids,
+        vec![UserId(0), UserId(1), UserId(2), UserId(3), UserId(4)]
+    );
+    let back = (UserId(2)..=UserId(4)).rev().collect::<Vec<_>>();
+    println!("back: {:?}", back);
+    assert_eq!(back, vec![UserId(4), UserId(3), UserId(2)]);
+    let even = (UserId(0)..UserId(7)).step_by(2).collect::<Vec<_>>();
+    assert_eq!(even, vec![UserId(0), UserId(2), UserId(4), UserId(6)]);
+    assert_eq!((UserId(3)..UserId(1)).count(), 0);
+    assert_eq!(Step::forward_checked(UserId(u64::MAX), 1), None);
+
+    println!();
+    println!("done");
+}
@@ -0,0 +1,66 @@
+//! `Try` and `FromResidual` impls, requires a nightly compiler:
+//!
+//! ```text
+//! cargo +nightly run --example try_trait --features nightly
+//! ```
+
+#![feature(try_trait_v2, try_trait_v2_residual)]
+
+use std::convert::Infallible;
+use std::ops::ControlFlow;
+
+use implem::implem;
+
+#[derive(Debug, PartialEq)]
+pub enum Fallible<T> {
+    Value(T),
+    Failure(String),
+}
+implem! {
+    impl(T) for Fallible<T> {
+        Try<Output = T, Residual = Fallible<Infallible>> {
+            |value| Fallible::Value(value),
+            |self| match self {
+                Fallible::Value(value) => ControlFlow::Continue(value),
+                Fallible::Failure(msg) => ControlFlow::Break(Fallible::Failure(msg)),
+            },
+        }
+        FromResidual {
+            |residual| match residual {
+                Fallible::Value(never) => match never {},
+                Fallible::Failure(msg) => Fallible::Failure(msg),
+            }
+        }
+    }
+    // recent nightlies require the residual to know the `Try` type it comes from
+    impl(T) for Fallible<Infallible> {
+        impl std::ops::Residual<T> {
+            type TryType = Fallible<T>;
+        }
+    }
+}
+
+fn parse(s: &str) -> Fallible<i32> {
+    match s.parse() {
+        Ok(n) => Fallible::Value(n),
+        Err(e) => Fallible::Failure(format!("`{}`: {}", s, e)),
+    }
+}
+
+fn sum(lft: &str, rgt: &str) -> Fallible<i32> {
+    Fallible::Value(parse(lft)? + parse(rgt)?)
+}
+
+fn main() {
+    println!("sum(\"1\", \"2\"): {:?}", sum("1", "2"));
+    assert_eq!(sum("1", "2"), Fallible::Value(3));
+    let failed = sum("1", "two");
+    println!("sum(\"1\", \"two\"): {:?}", failed);
+    assert_eq!(
+        failed,
+        Fallible::Failure("`two`: invalid digit found in string".into())
+    );
+
+    println!();
+    println!("done");
+}
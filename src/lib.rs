@@ -2,11 +2,113 @@
 //!
 //! Supported traits:
 //!
-//! - [`std::fmt::Display`], [`std::fmt::Debug`]
-//! - [`std::convert::From`]
+//! - [`std::fmt::Display`], [`std::fmt::Debug`], [`std::fmt::Binary`], [`std::fmt::Octal`],
+//!   [`std::fmt::LowerHex`], [`std::fmt::UpperHex`], [`std::fmt::Pointer`], [`std::fmt::LowerExp`],
+//!   [`std::fmt::UpperExp`]
+//! - `DisplayViaDebug` and `DebugViaDisplay`, implementing one formatting trait with the other
+//! - `DisplayAlt`, implementing `Display` with different outputs for `{}` and `{:#}`
+//! - `Debug { struct "Name" { ... } }`, `Debug { tuple "Name" (...) }` and `Debug { list [...] }`,
+//!   implementing `Debug` with [`std::fmt::Formatter::debug_struct`],
+//!   [`std::fmt::Formatter::debug_tuple`] and [`std::fmt::Formatter::debug_list`]
+//! - [`std::fmt::Write`], as `FmtWrite`
+//! - [`std::str::FromStr`], and `DisplayFromStr` implementing it alongside `Display`
+//! - `FromStrViaTryFrom`, implementing [`std::str::FromStr`] with `TryFrom<&str>`
+//! - [`std::convert::From`], [`std::convert::TryFrom`]
 //! - [`std::convert::Into`]
+//! - [`std::default::Default`]
+//! - [`std::clone::Clone`]
+//! - [`std::borrow::ToOwned`]
 //! - [`std::ops::Deref`]
 //! - [`std::ops::DerefMut`]
+//! - `DerefStr`, shorthand for `Deref<Target = str>`
+//! - `DerefSlice<T>`, shorthand for `Deref<Target = [T]>`
+//! - `Deref1 { T }`, shorthand for `Deref` and `DerefMut` to the first field of a tuple struct
+//! - [`std::ops::Index`], [`std::ops::IndexMut`]
+//! - [`std::ops::RangeBounds`]
+//! - [`std::ops::Add`], [`std::ops::Sub`], [`std::ops::Mul`], [`std::ops::Div`], [`std::ops::Rem`],
+//!   [`std::ops::BitAnd`], [`std::ops::BitOr`], [`std::ops::BitXor`], [`std::ops::Shl`],
+//!   [`std::ops::Shr`]
+//! - [`std::marker::Unpin`]
+//! - [`std::panic::UnwindSafe`], [`std::panic::RefUnwindSafe`]
+//! - [`std::marker::Send`] and [`std::marker::Sync`], as `unsafe Send` and `unsafe Sync`
+//! - [`std::error::Error`], and `ErrorType` implementing it alongside `Display` and `Debug`
+//! - `Transparent { Inner }`, the usual delegating impls of a newtype around `Inner`
+//! - [`std::cmp::PartialEq`]
+//! - `Comparable`, implementing [`PartialEq`], [`Eq`], [`PartialOrd`] and [`Ord`] through a key
+//! - `Hashable`, implementing [`std::hash::Hash`], [`PartialEq`] and [`Eq`] through a key
+//! - `Comparable + Hashable`, implementing all of the above through a single key
+//! - [`std::iter::Iterator`], [`std::iter::FusedIterator`]
+//! - [`std::hash::Hasher`], [`std::hash::BuildHasher`]
+//! - [`std::io::Read`], [`std::io::Write`], [`std::io::Seek`] (also as `IoSeek`),
+//!   [`std::io::BufRead`] (also as `IoBufRead`)
+//! - [`std::future::Future`], [`std::future::IntoFuture`], [`std::task::Wake`]
+//! - [`std::process::Termination`]
+//! - [`std::net::ToSocketAddrs`]
+//! - [`std::os::fd::AsRawFd`], [`std::os::fd::AsFd`], on unix only
+//! - [`std::alloc::GlobalAlloc`], as `unsafe GlobalAlloc`
+//! - with the `serde` feature: `serde::Serialize`, `serde::Deserialize`, and `SerdeViaStr`
+//!   implementing them through `Display` and `FromStr`
+//! - with the `futures` feature: `futures_core::Stream`, `futures_sink::Sink`
+//! - with the `tokio-io` feature: `tokio::io::AsyncRead`, `tokio::io::AsyncWrite`
+//! - with the `rayon` feature: `rayon::iter::IntoParallelIterator`
+//! - with the `num-traits` feature: `num_traits::Zero`, `num_traits::One`,
+//!   `num_traits::FromPrimitive`, `num_traits::ToPrimitive`
+//! - with the `quickcheck` feature: `quickcheck::Arbitrary`
+//! - with the `proptest` feature: `proptest::arbitrary::Arbitrary`, as `ProptestArbitrary`
+//! - with the `arbitrary` feature: `arbitrary::Arbitrary`, as `Arbitrary<'a>`
+//! - with the `rand` feature: `rand::distributions::Distribution<Self>` for `Standard` (`rand` 0.8),
+//!   as `RandStandard`
+//! - with the `bytes` feature: `bytes::Buf`, and `bytes::BufMut` as `unsafe BufMut`
+//! - with the `clap` feature: `clap::ValueEnum`
+//! - with the `approx` feature: `approx::AbsDiffEq`, `approx::RelativeEq`
+//! - with the `equivalent` feature: `equivalent::Equivalent`
+//! - with the `borsh` feature: `borsh::BorshSerialize`, `borsh::BorshDeserialize`
+//! - with the `ufmt` feature: `ufmt::uDisplay`, `ufmt::uDebug`
+//! - with the `defmt` feature: `defmt::Format`
+//! - with the `zeroize` feature: `zeroize::Zeroize`, and `zeroize::ZeroizeOnDrop` with its `Drop`
+//!   impl
+//! - with the `nightly` feature: [`FnOnce`], [`FnMut`], [`Fn`], `std::ops::Try`,
+//!   `std::ops::FromResidual`, `std::iter::Step`, and the `const` forms of `From`, `Into`,
+//!   `Default`, `Deref` and `DerefMut`
+//! - any other trait, as `impl MyTrait { $items }`
+//!
+//! Newtypes can get their usual impls in one line with [`impl_newtype!`], error enums with
+//! [`impl_error_type!`]. [`test_roundtrip!`] generates tests checking that `Display` and `FromStr`
+//! agree.
+//!
+//! # Cargo features
+//!
+//! Definitions of traits from other crates are behind Cargo features, all off by default so that
+//! `implem` stays dependency-free. The generated impls refer to these crates by absolute paths,
+//! like `::serde::Serialize`, so the crate using a definition must also depend on the crate it
+//! targets, in the major version given below:
+//!
+//! - `serde`: `serde` 1
+//! - `futures`: `futures-core` 0.3 and `futures-sink` 0.3, depending on the traits used
+//! - `tokio-io`: `tokio` 1
+//! - `rayon`: `rayon` 1
+//! - `num-traits`: `num-traits` 0.2
+//! - `quickcheck`: `quickcheck` 1
+//! - `proptest`: `proptest` 1
+//! - `arbitrary`: `arbitrary` 1
+//! - `rand`: `rand` 0.8
+//! - `bytes`: `bytes` 1
+//! - `clap`: `clap` 4
+//! - `approx`: `approx` 0.5
+//! - `equivalent`: `equivalent` 1
+//! - `borsh`: `borsh` 1
+//! - `ufmt`: `ufmt` 0.2
+//! - `defmt`: `defmt` 0.3
+//! - `zeroize`: `zeroize` 1
+//!
+//! The `nightly` feature enables definitions relying on unstable compiler features, such as `const`
+//! impls. The crate using them must be built with a nightly compiler and enable the
+//! `#![feature(...)]`s given in their section.
+//!
+//! Without its feature, a definition is a compile error naming the feature. The sections below
+//! covering gated definitions name their feature in their title. Their examples are not compiled as
+//! doc tests: the definitions are tested in `tests/`, against minimal stand-ins of the crates found
+//! in `tests/stubs`, and in `examples/` for the `nightly` ones.
 //!
 //! # Syntax
 //!
@@ -31,6 +133,38 @@
 //! instance, `from` for [`From`], `deref_mut` for [`std::ops::DerefMut`], or `fmt` for
 //! [`std::fmt::Display`].
 //!
+//! All generated impls are marked `#[automatically_derived]`, so lints that skip derived code skip
+//! them too.
+//!
+//! Unknown trait names are rejected at compile time, with an error listing the supported traits.
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! pub struct MyStruct;
+//! implem! {
+//!     for MyStruct {
+//!         Diplay {
+//!             |&self, fmt| write!(fmt, "MyStruct")
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Unbalanced angle brackets in the type after `for` get a dedicated error too, instead of a
+//! parse error from deep inside the macro; here the `>` after `T` is missing.
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! pub struct MyType<T>(T);
+//! implem! {
+//!     impl(T) for MyType<T where (T: std::fmt::Debug) {
+//!         Debug {
+//!             |&self, fmt| write!(fmt, "MyType({:?})", self.0)
+//!         }
+//!     }
+//! }
+//! ```
+//!
 //! # Examples
 //!
 //! ## `Display`, `Debug`, `From` and `Into`
@@ -65,152 +199,8235 @@
 //! }
 //! ```
 //!
-//! ## `Deref` and `DerefMut`
+//! ## Where clauses
+//!
+//! Where clauses are forwarded as is, so multiple bounds, lifetime bounds and higher-ranked trait
+//! bounds all work.
 //!
 //! ```rust
 //! # use implem::implem;
-//! pub struct MyStruct1 {
-//!     s: String,
+//! use std::fmt::Debug;
+//!
+//! pub struct MyVec<T>(Vec<T>);
+//! pub struct Callback<F>(F);
+//! pub struct Labeled<'a, T>(&'a str, T);
+//! implem! {
+//!     impl(T) for MyVec<T> where (T: Clone + Debug + PartialEq, Vec<T>: Debug) {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "{:?}", self.0)
+//!         }
+//!     }
+//!     impl(T) for MyVec<T> where (T: 'static + Debug) {
+//!         Debug {
+//!             |&self, fmt| write!(fmt, "MyVec({:?})", self.0)
+//!         }
+//!     }
+//!     impl(F) for Callback<F> where (for<'a> F: Fn(&'a str) -> &'a str) {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "{}", (self.0)("callback"))
+//!         }
+//!     }
+//!     impl('a, 'b, T) for Labeled<'a, T> where ('b: 'a, T: Debug + 'a,) {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "{}: {:?}", self.0, self.1)
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(MyVec(vec![1, 2]).to_string(), "[1, 2]");
+//! assert_eq!(format!("{:?}", MyVec(vec!["a"])), "MyVec([\"a\"])");
+//! fn prefix(s: &str) -> &str {
+//!     &s[..4]
 //! }
+//! assert_eq!(Callback(prefix).to_string(), "call");
+//! assert_eq!(Labeled("n", 7).to_string(), "n: 7");
+//! ```
+//!
+//! `implem!` never infers bounds: it cannot tell which type parameters a body relies on, so
+//! whatever the bodies need, like `T: Display` to format a field, goes in the where clause. The
+//! only requirements an arm implies are those of the trait itself, which must hold for the impl
+//! to compile:
+//!
+//! - `DerefMut` requires `Deref`, and `IndexMut<Idx>` requires `Index<Idx>`
+//! - `Error` requires `Debug` and `Display`
+//! - `FusedIterator` requires `Iterator`, `BufRead` requires `Read`
+//! - `FnMut` requires `FnOnce`, `Fn` requires `FnMut`
+//! - `Comparable` and `Hashable` require their key to implement `Ord`, resp. `Hash` and `Eq`
+//! - `DisplayViaDebug` requires `Debug`, `DebugViaDisplay` requires `Display`
+//!
+//! Bundles such as `Deref` with two closures, `ErrorType` or `DisplayFromStr` generate the
+//! required impls themselves.
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! pub struct Wrapper<T>(T);
 //! implem! {
-//!     for MyStruct1 {
-//!         Deref<Target = String> {
-//!             |&self| &self.s,
-//!             // next *optional* line implements `DerefMut` as well
-//!             |&mut self| &mut self.s,
+//!     // missing `where (T: std::fmt::Display)`
+//!     impl(T) for Wrapper<T> {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "[{}]", self.0)
 //!         }
 //!     }
 //! }
+//! ```
 //!
-//! pub struct MyStruct2 {
-//!     s: String,
+//! The flip side is that parameters the bodies do not use need no bounds, unlike with `derive`. A
+//! `Debug` ignoring a `PhantomData<T>` works for any `T`:
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::marker::PhantomData;
+//!
+//! pub struct Id<T>(u64, PhantomData<T>);
+//! implem! {
+//!     impl(T) for Id<T> {
+//!         Debug {
+//!             |&self, fmt| write!(fmt, "Id({:?})", self.0)
+//!         }
+//!     }
 //! }
+//!
+//! /// Not `Debug`.
+//! pub struct User;
+//! assert_eq!(format!("{:?}", Id::<User>(7, PhantomData)), "Id(7)");
+//! ```
+//!
+//! ## Const generics
+//!
+//! Type parameters are forwarded as is, const generics included.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct ArrayWrapper<T, const N: usize>([T; N]);
 //! implem! {
-//!     for MyStruct2 {
-//!         Deref<Target = String> {
-//!             |&self| &self.s
+//!     impl(T, const N: usize) for ArrayWrapper<T, N> where (T: std::fmt::Debug) {
+//!         Debug {
+//!             |&self, fmt| write!(fmt, "{}: {:?}", N, self.0)
 //!         }
-//!         // explicit `DerefMut` implementation
-//!         DerefMut {
-//!             |&mut self| &mut self.s
+//!         DerefSlice<T> {}
+//!     }
+//!     impl(const N: usize) for ArrayWrapper<u8, N> {
+//!         Default {
+//!             || Self([0; N])
+//!         }
+//!     }
+//! }
+//!
+//! let array = ArrayWrapper([1, 2, 3]);
+//! assert_eq!(format!("{:?}", array), "3: [1, 2, 3]");
+//! assert_eq!(array.len(), 3);
+//! let zeros: ArrayWrapper<u8, 4> = ArrayWrapper::default();
+//! assert_eq!(&zeros[..], &[0; 4]);
+//! ```
+//!
+//! ## Anonymous lifetimes
+//!
+//! The self type can use `'_` like any impl header. Rust does not allow `'_` in where clauses though,
+//! name the lifetime with `impl('a)` instead.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct MyType(u32);
+//! pub struct Wrapper<'a>(&'a str);
+//! pub struct Ref<'a, T>(&'a T);
+//! implem! {
+//!     for &'_ MyType {
+//!         Into<u32> {
+//!             |self| self.0
+//!         }
+//!     }
+//!     for Wrapper<'_> {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "wrapped {}", self.0)
+//!         }
+//!         DerefStr {}
+//!     }
+//!     impl(T) for Ref<'_, T> where (T: std::fmt::Display) {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "&{}", self.0)
+//!         }
+//!     }
+//!     impl('a, T) for Ref<'a, Vec<T>> where (T: 'a + std::fmt::Debug) {
+//!         Debug {
+//!             |&self, fmt| write!(fmt, "&{:?}", self.0)
+//!         }
+//!     }
+//! }
+//!
+//! let n: u32 = (&MyType(7)).into();
+//! assert_eq!(n, 7);
+//! let wrapper = Wrapper("cat");
+//! assert_eq!(wrapper.to_string(), "wrapped cat");
+//! assert_eq!(wrapper.len(), 3);
+//! assert_eq!(Ref(&7).to_string(), "&7");
+//! assert_eq!(format!("{:?}", Ref(&vec![1])), "&[1]");
+//! ```
+//!
+//! ## Foreign self types
+//!
+//! `implem!` does not lift the orphan rules: the self type can be foreign, a primitive for instance,
+//! only when the trait or one of its parameters is local. So the commutative version of a scalar
+//! `Mul` is fine, and spelled as another block.
+//!
+//! ```rust
+//! # use implem::implem;
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! pub struct Force(f64);
+//! implem! {
+//!     for Force {
+//!         Mul<f64, Output = Self> {
+//!             |self, k| Force(self.0 * k)
+//!         }
+//!     }
+//!     for f64 {
+//!         Mul<Force, Output = Force> {
+//!             |self, force| Force(self * force.0)
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(Force(1.5) * 2.0, Force(3.0));
+//! assert_eq!(2.0 * Force(1.5), Force(3.0));
+//! ```
+//!
+//! Implementing a foreign trait without local parameters for a foreign type is rejected by rustc with
+//! `E0117`, pointing at the self type after `for`.
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! implem! {
+//!     for f64 {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "{}N", self)
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ## Several impls of the same trait
+//!
+//! Trait definitions are processed one after the other, so a block can hold any number of impls of
+//! the same generic trait. Type parameters of the block are shared by all of them, even those that do
+//! not use them.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct MyType(String);
+//! implem! {
+//!     impl('a) for MyType {
+//!         From<String> {
+//!             |s| Self(s)
+//!         }
+//!         From<&'a str> {
+//!             |s| Self(s.to_string())
+//!         }
+//!         From<&'a String> {
+//!             |s| Self(s.clone())
+//!         }
+//!     }
+//! }
+//!
+//! let owned = String::from("cat");
+//! assert_eq!(MyType::from(&owned).0, "cat");
+//! assert_eq!(MyType::from("dog").0, "dog");
+//! assert_eq!(MyType::from(owned).0, "cat");
+//! ```
+//!
+//! ## Attributes
+//!
+//! Attributes placed before a trait definition, typically `#[cfg(...)]`, are forwarded to the
+//! generated impl(s).
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct MyStruct;
+//! implem! {
+//!     for MyStruct {
+//!         #[cfg(test)]
+//!         Display {
+//!             |&self, fmt| write!(fmt, "test build")
+//!         }
+//!         #[cfg(not(test))]
+//!         Display {
+//!             |&self, fmt| write!(fmt, "regular build")
+//!         }
+//!         #[cfg(any())]
+//!         Debug {
+//!             |&self, fmt| write!(fmt, "never compiled")
+//!         }
+//!         DebugViaDisplay {}
+//!     }
+//! }
+//!
+//! // doctests are not compiled with `cfg(test)`
+//! assert_eq!(MyStruct.to_string(), "regular build");
+//! assert_eq!(format!("{:?}", MyStruct), "regular build");
+//! ```
+//!
+//! ## Other traits
+//!
+//! Since the macro cannot know the methods of arbitrary traits, other traits are implemented with
+//! `impl $trait { $items }` where `$items` are the usual associated items of an impl block. The block's
+//! type parameters, self type and where clauses are still handled by the macro.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub trait Container {
+//!     type Item;
+//!     fn get(&self, idx: usize) -> Option<&Self::Item>;
+//! }
+//! pub trait Named<T> {
+//!     fn name(&self) -> T;
+//! }
+//!
+//! pub struct Stack<T> {
+//!     elems: Vec<T>,
+//! }
+//! implem! {
+//!     impl(T) for Stack<T> where (T: Clone) {
+//!         impl Container {
+//!             type Item = T;
+//!             fn get(&self, idx: usize) -> Option<&T> {
+//!                 self.elems.get(idx)
+//!             }
+//!         }
+//!         impl Named<String> {
+//!             fn name(&self) -> String {
+//!                 format!("stack of {}", self.elems.len())
+//!             }
+//!         }
+//!         Debug {
+//!             |&self, fmt| write!(fmt, "{}", self.name())
+//!         }
+//!     }
+//! }
+//!
+//! let stack = Stack { elems: vec!['a', 'b'] };
+//! assert_eq!(stack.get(1), Some(&'b'));
+//! assert_eq!(format!("{:?}", stack), "stack of 2");
+//! ```
+//!
+//! ## `Binary`
+//!
+//! Same closure shape as `Display`. Formatter flags such as `#` are left to the body.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Flags {
+//!     bits: u8,
+//! }
+//! implem! {
+//!     for Flags {
+//!         Binary {
+//!             |&self, fmt| std::fmt::Binary::fmt(&self.bits, fmt)
+//!         }
+//!     }
+//! }
+//!
+//! let flags = Flags { bits: 0b101 };
+//! assert_eq!(format!("{:b}", flags), "101");
+//! assert_eq!(format!("{:#b}", flags), "0b101");
+//! ```
+//!
+//! ## `Octal`
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Mode(u32);
+//! implem! {
+//!     for Mode {
+//!         Octal {
+//!             |&self, fmt| std::fmt::Octal::fmt(&self.0, fmt)
+//!         }
+//!     }
+//! }
+//!
+//! let mode = Mode(0o755);
+//! assert_eq!(format!("{:o}", mode), "755");
+//! assert_eq!(format!("{:#o}", mode), "0o755");
+//! ```
+//!
+//! ## `LowerHex`
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Wrapper<T>(T);
+//! implem! {
+//!     impl(T) for Wrapper<T> where (T: std::fmt::LowerHex) {
+//!         LowerHex {
+//!             |&self, fmt| std::fmt::LowerHex::fmt(&self.0, fmt)
+//!         }
+//!     }
+//! }
+//!
+//! let hash = Wrapper(0xbeefu32);
+//! assert_eq!(format!("{:x}", hash), "beef");
+//! assert_eq!(format!("{:#x}", hash), "0xbeef");
+//! assert_eq!(format!("{:08x}", hash), "0000beef");
+//! ```
+//!
+//! ## `UpperHex`
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Register(u16);
+//! implem! {
+//!     for Register {
+//!         LowerHex {
+//!             |&self, fmt| std::fmt::LowerHex::fmt(&self.0, fmt)
+//!         }
+//!         UpperHex {
+//!             |&self, fmt| std::fmt::UpperHex::fmt(&self.0, fmt)
+//!         }
+//!     }
+//! }
+//!
+//! let reg = Register(0xbeef);
+//! assert_eq!(format!("{:x}", reg), "beef");
+//! assert_eq!(format!("{:X}", reg), "BEEF");
+//! assert_eq!(format!("{:#06X}", reg), "0xBEEF");
+//! ```
+//!
+//! ## `Pointer`
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Handle {
+//!     ptr: *const u8,
+//! }
+//! implem! {
+//!     for Handle {
+//!         Pointer {
+//!             |&self, fmt| std::fmt::Pointer::fmt(&self.ptr, fmt)
+//!         }
+//!     }
+//! }
+//!
+//! let byte = 7u8;
+//! let handle = Handle { ptr: &byte };
+//! let formatted = format!("{:p}", handle);
+//! assert!(formatted.starts_with("0x"));
+//! assert_eq!(formatted, format!("{:p}", handle.ptr));
+//! ```
+//!
+//! ## `LowerExp`
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Measurement<T>(T);
+//! implem! {
+//!     impl(T) for Measurement<T> where (T: std::fmt::LowerExp) {
+//!         LowerExp {
+//!             |&self, fmt| std::fmt::LowerExp::fmt(&self.0, fmt)
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(format!("{:e}", Measurement(1.5e-3)), "1.5e-3");
+//! assert_eq!(format!("{:.2e}", Measurement(1234.5)), "1.23e3");
+//! ```
+//!
+//! ## `UpperExp`
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Measurement(f64);
+//! implem! {
+//!     for Measurement {
+//!         Display {
+//!             |&self, fmt| std::fmt::Display::fmt(&self.0, fmt)
+//!         }
+//!         LowerExp {
+//!             |&self, fmt| std::fmt::LowerExp::fmt(&self.0, fmt)
+//!         }
+//!         UpperExp {
+//!             |&self, fmt| std::fmt::UpperExp::fmt(&self.0, fmt)
 //!         }
 //!     }
 //! }
+//!
+//! let x = Measurement(1500.0);
+//! assert_eq!(format!("{}", x), "1500");
+//! assert_eq!(format!("{:e}", x), "1.5e3");
+//! assert_eq!(format!("{:E}", x), "1.5E3");
 //! ```
+//!
+//! ## `|&self, fmt|` receivers in formatting traits
+//!
+//! Formatting traits (`Display`, `Debug`, `Binary`, ...) take `|&self, fmt|`. Their `fmt` method
+//! takes `&self`, so `self` is a reference in the body even for `Copy` types, and `|self, fmt|` is
+//! rejected with an error pointing to `|&self, fmt|`. Dereference `self` in the body to get a copy.
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! #[derive(Clone, Copy)]
+//! pub struct Celsius(f64);
+//! implem! {
+//!     for Celsius {
+//!         Display {
+//!             |self, fmt| write!(fmt, "{}°C", self.0)
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ## `{:#}` in `Display`
+//!
+//! The formatter is available in the body, so `fmt.alternate()` tells `{}` and `{:#}` apart.
+//! `DisplayAlt` writes the `if` for you, with a `short` closure for `{}` and an `alt` one for `{:#}`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Version {
+//!     major: u32,
+//!     minor: u32,
+//! }
+//! implem! {
+//!     for Version {
+//!         Display {
+//!             |&self, fmt| if fmt.alternate() {
+//!                 write!(fmt, "version {}.{}", self.major, self.minor)
+//!             } else {
+//!                 write!(fmt, "{}.{}", self.major, self.minor)
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! pub struct Duration {
+//!     secs: u64,
+//! }
+//! implem! {
+//!     for Duration {
+//!         DisplayAlt {
+//!             short: |&self, fmt| write!(fmt, "{}s", self.secs),
+//!             alt: |&self, fmt| write!(fmt, "{}m {}s", self.secs / 60, self.secs % 60),
+//!         }
+//!     }
+//! }
+//!
+//! let version = Version { major: 1, minor: 4 };
+//! assert_eq!(format!("{}", version), "1.4");
+//! assert_eq!(format!("{:#}", version), "version 1.4");
+//! let duration = Duration { secs: 135 };
+//! assert_eq!(format!("{}", duration), "135s");
+//! assert_eq!(format!("{:#}", duration), "2m 15s");
+//! ```
+//!
+//! ## `Debug` builders
+//!
+//! `Debug { struct "Name" { "field" => value, ... } }` generates the
+//! [`debug_struct`](std::fmt::Formatter::debug_struct) chain, each value must be a reference to
+//! something implementing `Debug`. The output respects `{:#?}` like derived impls.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Point {
+//!     x: f64,
+//!     y: f64,
+//! }
+//! implem! {
+//!     for Point {
+//!         Debug {
+//!             struct "Point" { "x" => &self.x, "y" => &self.y }
+//!         }
+//!     }
+//! }
+//!
+//! let point = Point { x: 1.0, y: 2.0 };
+//! assert_eq!(format!("{:?}", point), "Point { x: 1.0, y: 2.0 }");
+//! assert_eq!(format!("{:#?}", point), "Point {\n    x: 1.0,\n    y: 2.0,\n}");
+//!
+//! // Fields can be computed, or left out.
+//! pub struct Buffer(Vec<u8>);
+//! implem! {
+//!     for Buffer {
+//!         Debug {
+//!             struct "Buffer" { "len" => &self.0.len(), }
+//!         }
+//!     }
+//! }
+//! assert_eq!(format!("{:?}", Buffer(vec![1, 2, 3])), "Buffer { len: 3 }");
+//! ```
+//!
+//! Fields are `"label" => expr` pairs:
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! pub struct Point {
+//!     x: f64,
+//! }
+//! implem! {
+//!     for Point {
+//!         Debug {
+//!             struct "Point" { x: &self.x }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! `Debug { list [entries] }` generates the [`debug_list`](std::fmt::Formatter::debug_list) chain,
+//! `entries` being anything `IntoIterator` over `Debug` items. Handy for wrappers that should print
+//! as a plain list.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Stack(Vec<i32>);
+//! implem! {
+//!     for Stack {
+//!         Debug {
+//!             list [&self.0]
+//!         }
+//!     }
+//! }
+//!
+//! let stack = Stack(vec![1, 2, 3]);
+//! assert_eq!(format!("{:?}", stack), "[1, 2, 3]");
+//! assert_eq!(format!("{:#?}", stack), "[\n    1,\n    2,\n    3,\n]");
+//!
+//! pub struct Evens(u32);
+//! implem! {
+//!     for Evens {
+//!         Debug {
+//!             list [(0..self.0).map(|n| 2 * n)]
+//!         }
+//!     }
+//! }
+//! assert_eq!(format!("{:?}", Evens(3)), "[0, 2, 4]");
+//! ```
+//!
+//! `Debug { tuple "Name" (fields) }` generates the [`debug_tuple`](std::fmt::Formatter::debug_tuple)
+//! chain, each field being a reference to something implementing `Debug`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Pair(i32, i32);
+//! implem! {
+//!     for Pair {
+//!         Debug {
+//!             tuple "Pair" (&self.0, &self.1)
+//!         }
+//!     }
+//! }
+//!
+//! let pair = Pair(1, 2);
+//! assert_eq!(format!("{:?}", pair), "Pair(1, 2)");
+//! assert_eq!(format!("{:#?}", pair), "Pair(\n    1,\n    2,\n)");
+//! ```
+//!
+//! ## `DisplayViaDebug` and `DebugViaDisplay`
+//!
+//! Body-less shorthands implementing `Display` by delegating to `Debug`, and the other way around.
+//!
+//! ```rust
+//! # use implem::implem;
+//! #[derive(Debug)]
+//! pub struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//! implem! {
+//!     for Point {
+//!         DisplayViaDebug {}
+//!     }
+//! }
+//!
+//! pub struct Name(String);
+//! implem! {
+//!     for Name {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "<{}>", self.0)
+//!         }
+//!         DebugViaDisplay {}
+//!     }
+//! }
+//!
+//! let point = Point { x: 1, y: 2 };
+//! assert_eq!(format!("{}", point), format!("{:?}", point));
+//! let name = Name("cat".into());
+//! assert_eq!(format!("{:?}", name), "<cat>");
+//! assert_eq!(format!("{}", name), format!("{:?}", name));
+//! ```
+//!
+//! ## `FmtWrite`
+//!
+//! Implements [`std::fmt::Write`], named `FmtWrite` to avoid confusion with [`std::io::Write`]. The
+//! first closure defines `write_str`, the second *optional* one overrides `write_char`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::fmt::Write;
+//!
+//! pub struct Sink {
+//!     buf: String,
+//!     chars: usize,
+//! }
+//! implem! {
+//!     for Sink {
+//!         FmtWrite {
+//!             |&mut self, s| {
+//!                 self.buf.push_str(s);
+//!                 Ok(())
+//!             },
+//!             |&mut self, c| {
+//!                 self.chars += 1;
+//!                 self.buf.push(c);
+//!                 Ok(())
+//!             },
+//!         }
+//!     }
+//! }
+//!
+//! let mut sink = Sink { buf: String::new(), chars: 0 };
+//! write!(sink, "{} + {}", 1, 2).unwrap();
+//! sink.write_char('!').unwrap();
+//! assert_eq!(sink.buf, "1 + 2!");
+//! assert_eq!(sink.chars, 1);
+//! ```
+//!
+//! ## `Error`
+//!
+//! The body is optional: `Error {}` keeps the default `source`, while a `|&self|` closure overrides
+//! [`std::error::Error::source`].
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Cause;
+//! implem! {
+//!     for Cause {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "the cause")
+//!         }
+//!         Debug {
+//!             |&self, fmt| write!(fmt, "Cause")
+//!         }
+//!         Error {}
+//!     }
+//! }
+//!
+//! pub struct MyError {
+//!     cause: Cause,
+//! }
+//! implem! {
+//!     for MyError {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "my error")
+//!         }
+//!         Debug {
+//!             |&self, fmt| write!(fmt, "MyError({:?})", self.cause)
+//!         }
+//!         Error {
+//!             |&self| Some(&self.cause)
+//!         }
+//!     }
+//! }
+//!
+//! use std::error::Error;
+//! let err = MyError { cause: Cause };
+//! let source = err.source().expect("`MyError` has a source");
+//! assert_eq!(source.to_string(), "the cause");
+//! assert!(source.downcast_ref::<Cause>().is_some());
+//! assert!(source.source().is_none());
+//! ```
+//!
+//! ## `ErrorType`
+//!
+//! Bundles `Display`, `Debug` and `Error`: the first two closures define `fmt` for `Display` and
+//! `Debug`, the third *optional* one defines `source`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::error::Error;
+//!
+//! pub struct ParseError {
+//!     line: usize,
+//!     cause: std::num::ParseIntError,
+//! }
+//! implem! {
+//!     for ParseError {
+//!         ErrorType {
+//!             |&self, fmt| write!(fmt, "parse error on line {}", self.line),
+//!             |&self, fmt| write!(fmt, "ParseError {{ line: {}, cause: {:?} }}", self.line, self.cause),
+//!             |&self| Some(&self.cause),
+//!         }
+//!     }
+//! }
+//!
+//! fn parse(line: usize, s: &str) -> Result<u32, Box<dyn Error>> {
+//!     let n = s.parse().map_err(|cause| ParseError { line, cause })?;
+//!     Ok(n)
+//! }
+//!
+//! let err = parse(3, "three").unwrap_err();
+//! assert_eq!(err.to_string(), "parse error on line 3");
+//! assert!(format!("{:?}", err).starts_with("ParseError { line: 3"));
+//! let cause = err.source().unwrap();
+//! assert_eq!(cause.to_string(), "invalid digit found in string");
+//! assert!(cause.downcast_ref::<std::num::ParseIntError>().is_some());
+//! ```
+//!
+//! ## `From` for enums
+//!
+//! Nothing specific to structs: `From` bodies can build any variant, usually to inject the source
+//! type into a sum type.
+//!
+//! ```rust
+//! # use implem::implem;
+//! #[derive(Debug, PartialEq)]
+//! pub enum Value {
+//!     Int(i64),
+//!     Float(f64),
+//!     Str(String),
+//!     Pair(i64, i64),
+//!     Range { start: i64, end: i64 },
+//! }
+//! implem! {
+//!     for Value {
+//!         From<i64> {
+//!             |n| Value::Int(n)
+//!         }
+//!         From<f64> {
+//!             |f| Self::Float(f)
+//!         }
+//!         From<&str> {
+//!             |s| Self::Str(s.into())
+//!         }
+//!         From<(i64, i64)> {
+//!             |(l, r)| Self::Pair(l, r)
+//!         }
+//!         From<std::ops::Range<i64>> {
+//!             |std::ops::Range { start, end }| Self::Range { start, end }
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(Value::from(7), Value::Int(7));
+//! assert_eq!(Value::from(0.5), Value::Float(0.5));
+//! assert_eq!(Value::from("seven"), Value::Str("seven".into()));
+//! assert_eq!(Value::from((1, 2)), Value::Pair(1, 2));
+//! let range: Value = (0..3).into();
+//! assert_eq!(range, Value::Range { start: 0, end: 3 });
+//! ```
+//!
+//! ## Destructuring in `From`
+//!
+//! The argument of `From` is a pattern, so tuples (or structs) can be destructured directly.
+//!
+//! ```rust
+//! # use implem::implem;
+//! #[derive(Debug, PartialEq)]
+//! pub struct Point {
+//!     x: f64,
+//!     y: f64,
+//! }
+//! #[derive(Debug, PartialEq)]
+//! pub struct Point3 {
+//!     x: f64,
+//!     y: f64,
+//!     z: f64,
+//! }
+//! implem! {
+//!     for Point {
+//!         From<(f64, f64)> {
+//!             |(x, y)| Self { x, y }
+//!         }
+//!         From<[f64; 2]> {
+//!             |[x, y]| Self { x, y }
+//!         }
+//!     }
+//!     for Point3 {
+//!         From<(f64, f64, f64)> {
+//!             |(x, y, z)| Self { x, y, z }
+//!         }
+//!         From<(Point, f64)> {
+//!             |(Point { x, y }, z)| Self { x, y, z }
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(Point::from((1.0, 2.0)), Point { x: 1.0, y: 2.0 });
+//! assert_eq!(Point::from([1.0, 2.0]), Point { x: 1.0, y: 2.0 });
+//! let p: Point3 = (1.0, 2.0, 3.0).into();
+//! assert_eq!(p, Point3 { x: 1.0, y: 2.0, z: 3.0 });
+//! assert_eq!(Point3::from((Point { x: 1.0, y: 2.0 }, 3.0)), p);
+//! ```
+//!
+//! ## `From<Cow<str>>`
+//!
+//! Source types with lifetimes need them in `impl(...)`. A string newtype can take a `Cow` to accept
+//! both `&str` and `String`, only allocating when the `Cow` is borrowed.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::borrow::Cow;
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Name(String);
+//! implem! {
+//!     impl('a) for Name {
+//!         From<Cow<'a, str>> {
+//!             |cow| Self(cow.into_owned())
+//!         }
+//!     }
+//! }
+//!
+//! let borrowed = Name::from(Cow::Borrowed("ada"));
+//! let owned = Name::from(Cow::Owned::<str>("ada".to_string()));
+//! assert_eq!(borrowed, owned);
+//!
+//! // An owned `Cow` hands over its buffer.
+//! let buffer = String::from("grace");
+//! let ptr = buffer.as_ptr();
+//! let name: Name = Cow::<str>::Owned(buffer).into();
+//! assert_eq!(name.0.as_ptr(), ptr);
+//!
+//! fn greet<'a>(name: impl Into<Cow<'a, str>>) -> Name {
+//!     Name::from(name.into())
+//! }
+//! assert_eq!(greet("ada"), greet(String::from("ada")));
+//! ```
+//!
+//! ## Naming the receiver in `Into`
+//!
+//! The closure argument of `Into` is the consumed `self`, but it does not have to be *called* `self`:
+//! `|me| ...` binds `self` to `me`, which avoids shadowing issues when the body itself contains
+//! closures or nested impls.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Celsius {
+//!     degrees: f64,
+//! }
+//! implem! {
+//!     for Celsius {
+//!         Into<f64> {
+//!             |me| me.degrees
+//!         }
+//!     }
+//! }
+//!
+//! let degrees: f64 = Celsius { degrees: 21.5 }.into();
+//! assert_eq!(degrees, 21.5);
+//! ```
+//!
+//! ## `TryFrom`
+//!
+//! The error type is declared first, as in a manual impl. A block can hold any number of `TryFrom`
+//! definitions, with the same error type or not.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::convert::{TryFrom, TryInto};
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Percent(u8);
+//! implem! {
+//!     for Percent {
+//!         TryFrom<u32> {
+//!             type Error = String;
+//!             |n| if n <= 100 {
+//!                 Ok(Self(n as u8))
+//!             } else {
+//!                 Err(format!("{} is not a percentage", n))
+//!             }
+//!         }
+//!         TryFrom<i32> {
+//!             type Error = String;
+//!             |n| u32::try_from(n).map_err(|e| e.to_string())?.try_into()
+//!         }
+//!         TryFrom<&'static str> {
+//!             type Error = std::num::ParseIntError;
+//!             |s| s.trim_end_matches('%').parse().map(Self)
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(Percent::try_from(42u32), Ok(Percent(42)));
+//! assert_eq!(Percent::try_from(420u32), Err("420 is not a percentage".to_string()));
+//! assert!(Percent::try_from(-1i32).is_err());
+//! assert_eq!(Percent::try_from(7i32), Ok(Percent(7)));
+//! assert_eq!(Percent::try_from("12%"), Ok(Percent(12)));
+//! let parsed: Result<Percent, _> = "twelve".try_into();
+//! assert!(parsed.is_err());
+//! ```
+//!
+//! `FromStrViaTryFrom {}` implements `FromStr` by delegating to `TryFrom<&str>`, so that `parse` and
+//! `try_from` cannot disagree. `Err` is the error of `TryFrom`, or any type it converts into with
+//! `FromStrViaTryFrom<Err = MyErr> {}`. The `TryFrom` impl must accept any `&str`, not only
+//! `&'static str`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::convert::TryFrom;
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Tag(String);
+//! implem! {
+//!     for Tag {
+//!         TryFrom<&str> {
+//!             type Error = String;
+//!             |s| if !s.is_empty() && s.chars().all(char::is_alphanumeric) {
+//!                 Ok(Self(s.into()))
+//!             } else {
+//!                 Err(format!("invalid tag `{}`", s))
+//!             }
+//!         }
+//!         FromStrViaTryFrom {}
+//!     }
+//! }
+//!
+//! for input in ["hello", "", "a b"] {
+//!     assert_eq!(input.parse::<Tag>(), Tag::try_from(input));
+//! }
+//! assert_eq!("hello".parse(), Ok(Tag("hello".into())));
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Invalid(String);
+//! implem! {
+//!     for Invalid {
+//!         From<String> {
+//!             |msg| Self(msg)
+//!         }
+//!     }
+//! }
+//! #[derive(Debug, PartialEq)]
+//! pub struct Label(Tag);
+//! implem! {
+//!     for Label {
+//!         TryFrom<&str> {
+//!             type Error = String;
+//!             |s| Tag::try_from(s).map(Self)
+//!         }
+//!         FromStrViaTryFrom<Err = Invalid> {}
+//!     }
+//! }
+//! assert_eq!("a b".parse::<Label>(), Err(Invalid("invalid tag `a b`".into())));
+//! ```
+//!
+//! ## `FromStr` and `DisplayFromStr`
+//!
+//! `DisplayFromStr` bundles `Display` (first closure) and `FromStr` (second closure), which are
+//! usually written together so that `val.to_string().parse()` gives back `val`. Its error type is
+//! `String` unless specified with `DisplayFromStr<Err = MyErr>`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! #[derive(Debug, PartialEq)]
+//! pub struct Version {
+//!     major: u32,
+//!     minor: u32,
+//! }
+//! implem! {
+//!     for Version {
+//!         DisplayFromStr {
+//!             |&self, fmt| write!(fmt, "{}.{}", self.major, self.minor),
+//!             |s| {
+//!                 let (major, minor) = s.split_once('.').ok_or("expected `.`")?;
+//!                 let parse = |n: &str| n.parse::<u32>().map_err(|e| e.to_string());
+//!                 Ok(Self { major: parse(major)?, minor: parse(minor)? })
+//!             },
+//!         }
+//!     }
+//! }
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Port(u16);
+//! implem! {
+//!     for Port {
+//!         Display {
+//!             |&self, fmt| self.0.fmt(fmt)
+//!         }
+//!         FromStr<Err = std::num::ParseIntError> {
+//!             |s| s.parse().map(Self)
+//!         }
+//!     }
+//! }
+//!
+//! for val in [Version { major: 0, minor: 2 }, Version { major: 13, minor: 7 }] {
+//!     assert_eq!(val.to_string().parse::<Version>(), Ok(val));
+//! }
+//! assert!("1-2".parse::<Version>().is_err());
+//! assert_eq!(Port(8080).to_string().parse::<Port>(), Ok(Port(8080)));
+//! ```
+//!
+//! ## `Default`
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Counter(u32);
+//! implem! {
+//!     for Counter {
+//!         Default {
+//!             || Self(7)
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(Counter::default().0, 7);
+//! ```
+//!
+//! ## `Clone`
+//!
+//! For types where cloning is not field-by-field, or that have fields `#[derive(Clone)]` cannot
+//! handle. A `Copy` type can add `impl Copy {}` and clone with `*self`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! static CLONES: AtomicUsize = AtomicUsize::new(0);
+//!
+//! /// Not `Clone`, the cache is dropped when cloning.
+//! pub struct Cache(Option<String>);
+//!
+//! pub struct Tree {
+//!     children: Vec<Tree>,
+//!     cache: Cache,
+//! }
+//! implem! {
+//!     for Tree {
+//!         Clone {
+//!             |&self| {
+//!                 CLONES.fetch_add(1, Ordering::SeqCst);
+//!                 Self {
+//!                     children: self.children.clone(),
+//!                     cache: Cache(None),
+//!                 }
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let tree = Tree {
+//!     children: vec![
+//!         Tree { children: vec![], cache: Cache(None) },
+//!         Tree { children: vec![], cache: Cache(Some("leaf".into())) },
+//!     ],
+//!     cache: Cache(Some("root".into())),
+//! };
+//! let copy = tree.clone();
+//! // the root and its two children
+//! assert_eq!(CLONES.load(Ordering::SeqCst), 3);
+//! assert_eq!(copy.children.len(), 2);
+//! assert!(copy.cache.0.is_none());
+//! assert!(copy.children[1].cache.0.is_none());
+//! assert_eq!(tree.cache.0.as_deref(), Some("root"));
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Id(u32);
+//! implem! {
+//!     for Id {
+//!         Clone {
+//!             |&self| *self
+//!         }
+//!         impl Copy {}
+//!     }
+//! }
+//! let id = Id(7);
+//! let other = id;
+//! assert_eq!(id, other);
+//! ```
+//!
+//! ## `ToOwned`
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::borrow::{Borrow, Cow};
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Id {
+//!     id: u32,
+//! }
+//! #[derive(Debug, PartialEq)]
+//! pub struct OwnedId {
+//!     inner: Id,
+//! }
+//! impl Borrow<Id> for OwnedId {
+//!     fn borrow(&self) -> &Id {
+//!         &self.inner
+//!     }
+//! }
+//! implem! {
+//!     for Id {
+//!         ToOwned<Owned = OwnedId> {
+//!             |&self| OwnedId { inner: Id { id: self.id } }
+//!         }
+//!     }
+//! }
+//!
+//! let id = Id { id: 7 };
+//! let cow: Cow<Id> = Cow::Borrowed(&id);
+//! assert_eq!(cow.id, 7);
+//! assert_eq!(cow.into_owned(), OwnedId { inner: Id { id: 7 } });
+//! ```
+//!
+//! ## `Deref` and `DerefMut`
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct MyStruct1 {
+//!     s: String,
+//! }
+//! implem! {
+//!     for MyStruct1 {
+//!         Deref<Target = String> {
+//!             |&self| &self.s,
+//!             // next *optional* line implements `DerefMut` as well
+//!             |&mut self| &mut self.s,
+//!         }
+//!     }
+//! }
+//!
+//! pub struct MyStruct2 {
+//!     s: String,
+//! }
+//! implem! {
+//!     for MyStruct2 {
+//!         Deref<Target = String> {
+//!             |&self| &self.s
+//!         }
+//!         // explicit `DerefMut` implementation
+//!         DerefMut {
+//!             |&mut self| &mut self.s
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ## `DerefStr`
+//!
+//! Shorthand for `Deref<Target = str>`. Without a body, it dereferences to the first field of a tuple
+//! struct; a `|&self|` closure can select something else.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct StrWrapper(String);
+//! implem! {
+//!     for StrWrapper {
+//!         DerefStr {}
+//!     }
+//! }
+//!
+//! pub struct Named {
+//!     inner: String,
+//! }
+//! implem! {
+//!     for Named {
+//!         DerefStr {
+//!             |&self| self.inner.as_str()
+//!         }
+//!     }
+//! }
+//!
+//! fn needs_str(s: &str) -> usize {
+//!     s.len()
+//! }
+//!
+//! let w = StrWrapper("hello".to_string());
+//! assert_eq!(needs_str(&w), 5);
+//! let n = Named { inner: "cat".to_string() };
+//! assert_eq!(needs_str(&n), 3);
+//! assert!(n.starts_with('c'));
+//! ```
+//!
+//! ## `DerefSlice`
+//!
+//! Shorthand for `Deref<Target = [T]>`, with the same optional body as `DerefStr`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Items(Vec<u32>);
+//! implem! {
+//!     for Items {
+//!         DerefSlice<u32> {}
+//!     }
+//! }
+//!
+//! pub struct Data<T> {
+//!     data: Vec<T>,
+//! }
+//! implem! {
+//!     impl(T) for Data<T> {
+//!         DerefSlice<T> {
+//!             |&self| self.data.as_slice()
+//!         }
+//!     }
+//! }
+//!
+//! let items = Items(vec![1, 2, 3]);
+//! assert_eq!(items[1], 2);
+//! assert_eq!(items.len(), 3);
+//! assert_eq!(items.iter().sum::<u32>(), 6);
+//!
+//! let data = Data { data: vec!["a", "b"] };
+//! assert_eq!(&data[..], &["a", "b"]);
+//! ```
+//!
+//! Indexing already works through the deref, but `DerefSlice` can be combined with explicit `Index`
+//! and `IndexMut` definitions on the same type, as they do not overlap: `Deref` is a different
+//! trait, not a blanket `Index` impl. Note however that once the type has an `Index` impl, indexing
+//! no longer goes through the deref, so other index types such as ranges need their own definitions
+//! or an explicit deref.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Samples(Vec<i16>);
+//! implem! {
+//!     for Samples {
+//!         DerefSlice<i16> {
+//!             |&self| &self.0
+//!         }
+//!         DerefMut {
+//!             |&mut self| self.0.as_mut_slice()
+//!         }
+//!         Index<usize, Output = i16> {
+//!             |&self, idx| &self.0[idx],
+//!             |&mut self, idx| &mut self.0[idx],
+//!         }
+//!     }
+//! }
+//!
+//! fn first<S: std::ops::Index<usize, Output = i16>>(samples: &S) -> i16 {
+//!     samples[0]
+//! }
+//!
+//! let mut samples = Samples(vec![1, 2, 3]);
+//! samples[0] = 10;
+//! assert_eq!(first(&samples), 10);
+//! assert_eq!(&samples[1], &2);
+//! assert_eq!(&(*samples)[1..], &[2, 3]);
+//! (*samples)[1..].copy_from_slice(&[20, 30]);
+//! assert_eq!(samples.iter().sum::<i16>(), 60);
+//! ```
+//!
+//! ```rust,compile_fail,E0308
+//! # use implem::implem;
+//! pub struct Samples(Vec<i16>);
+//! implem! {
+//!     for Samples {
+//!         DerefSlice<i16> {}
+//!         Index<usize, Output = i16> {
+//!             |&self, idx| &self.0[idx]
+//!         }
+//!     }
+//! }
+//!
+//! let samples = Samples(vec![1, 2, 3]);
+//! let _ = &samples[1..];
+//! ```
+//!
+//! ## `Deref1`
+//!
+//! Macros cannot inspect the fields of a type, but for tuple structs wrapping a single value, giving
+//! the type of the first field is enough: `Deref1 { T }` implements `Deref<Target = T>` and
+//! `DerefMut` to `self.0`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Wrapper(String);
+//! pub struct Labeled<T>(Vec<T>, &'static str);
+//! implem! {
+//!     for Wrapper {
+//!         Deref1 { String }
+//!     }
+//!     impl(T) for Labeled<T> {
+//!         Deref1 { Vec<T> }
+//!     }
+//! }
+//!
+//! let mut wrapper = Wrapper("cat".into());
+//! wrapper.push('s');
+//! assert_eq!(*wrapper, "cats");
+//!
+//! let mut labeled = Labeled(vec![1, 2], "numbers");
+//! labeled.push(3);
+//! assert_eq!(labeled.len(), 3);
+//! assert_eq!(labeled.1, "numbers");
+//! ```
+//!
+//! ## Binary operators
+//!
+//! `Add`, `Sub`, `Mul`, `Div`, `Rem`, `BitAnd`, `BitOr`, `BitXor`, `Shl` and `Shr` take the right-hand
+//! side type and the output type as `<Rhs, Output = Out>`, `<Output = Out>` when `Rhs` is `Self`. The
+//! closure gets `self` and the right-hand side, whose type annotation is optional. References work as
+//! any other type for both the right-hand side and the self type.
+//!
+//! ```rust
+//! # use implem::implem;
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! pub struct Vec2(i32, i32);
+//! implem! {
+//!     for Vec2 {
+//!         Add<Output = Self> {
+//!             |self, rhs| Vec2(self.0 + rhs.0, self.1 + rhs.1)
+//!         }
+//!         Add<&Self, Output = Self> {
+//!             |self, rhs: &Self| Vec2(self.0 + rhs.0, self.1 + rhs.1)
+//!         }
+//!         Sub<&mut Self, Output = Self> {
+//!             |self, rhs| {
+//!                 rhs.0 = 0;
+//!                 Vec2(self.0 - rhs.0, self.1 - rhs.1)
+//!             }
+//!         }
+//!         Mul<i32, Output = Self> {
+//!             |self, k: i32| Vec2(self.0 * k, self.1 * k)
+//!         }
+//!     }
+//!     impl('a) for &'a Vec2 {
+//!         Add<&'a Vec2, Output = Vec2> {
+//!             |self, rhs| Vec2(self.0 + rhs.0, self.1 + rhs.1)
+//!         }
+//!         Shl<u32, Output = Vec2> {
+//!             |this, n| Vec2(this.0 << n, this.1 << n)
+//!         }
+//!     }
+//! }
+//!
+//! let (a, b) = (Vec2(1, 2), Vec2(10, 20));
+//! assert_eq!(a + b, Vec2(11, 22));
+//! assert_eq!(a + &b, Vec2(11, 22));
+//! assert_eq!(&a + &b, Vec2(11, 22));
+//! let mut c = Vec2(5, 5);
+//! assert_eq!(b - &mut c, Vec2(10, 15));
+//! assert_eq!(c, Vec2(0, 5));
+//! assert_eq!(a * 3, Vec2(3, 6));
+//! assert_eq!(&a << 2, Vec2(4, 8));
+//! ```
+//!
+//! Operators need an `Output`:
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! pub struct Meters(u32);
+//! implem! {
+//!     for Meters {
+//!         Add<Self> {
+//!             |self, rhs| Meters(self.0 + rhs.0)
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ## `Index` and `IndexMut`
+//!
+//! `Index<Idx, Output = T>` takes a closure for `index`, and optionally a second one implementing
+//! `IndexMut<Idx>` as well. Any index type works, including ranges; note that the `Output` of a range
+//! index is a slice `[T]`, not a `T`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo};
+//!
+//! pub struct Buffer<T> {
+//!     items: Vec<T>,
+//! }
+//! implem! {
+//!     impl(T) for Buffer<T> {
+//!         Index<usize, Output = T> {
+//!             |&self, idx| &self.items[idx],
+//!             |&mut self, idx| &mut self.items[idx],
+//!         }
+//!         Index<Range<usize>, Output = [T]> {
+//!             |&self, range| &self.items[range],
+//!             |&mut self, range| &mut self.items[range],
+//!         }
+//!         Index<RangeFrom<usize>, Output = [T]> {
+//!             |&self, range| &self.items[range]
+//!         }
+//!         Index<RangeTo<usize>, Output = [T]> {
+//!             |&self, range| &self.items[range]
+//!         }
+//!         Index<RangeFull, Output = [T]> {
+//!             |&self, range| &self.items[range]
+//!         }
+//!         Index<RangeInclusive<usize>, Output = [T]> {
+//!             |&self, range| &self.items[range]
+//!         }
+//!     }
+//! }
+//!
+//! let mut buffer = Buffer { items: vec![1, 2, 3, 4, 5] };
+//! buffer[0] = 10;
+//! assert_eq!(buffer[0], 10);
+//! assert_eq!(&buffer[1..3], &[2, 3]);
+//! assert_eq!(&buffer[3..], &[4, 5]);
+//! assert_eq!(&buffer[..2], &[10, 2]);
+//! assert_eq!(&buffer[..], &[10, 2, 3, 4, 5]);
+//! assert_eq!(&buffer[1..=3], &[2, 3, 4]);
+//! buffer[1..3].copy_from_slice(&[20, 30]);
+//! assert_eq!(&buffer[..], &[10, 20, 30, 4, 5]);
+//! ```
+//!
+//! A standalone `IndexMut<Idx>` definition requires `Index<Idx>` to be implemented, by another
+//! definition or a manual impl. Without it the compiler reports the missing `Index<Idx>` impl, the
+//! two-closure `Index` form above is usually the simplest fix.
+//!
+//! ```rust,compile_fail,E0277
+//! # use implem::implem;
+//! pub struct Buffer(Vec<u8>);
+//! implem! {
+//!     for Buffer {
+//!         IndexMut<usize> {
+//!             |&mut self, idx| &mut self.0[idx]
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ## `RangeBounds`
+//!
+//! Takes two closures, for `start_bound` and `end_bound` respectively.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::ops::{Bound, RangeBounds};
+//!
+//! pub struct Span<T> {
+//!     lo: T,
+//!     hi: T,
+//! }
+//! implem! {
+//!     impl(T) for Span<T> {
+//!         RangeBounds<T> {
+//!             |&self| Bound::Included(&self.lo),
+//!             |&self| Bound::Excluded(&self.hi),
+//!         }
+//!     }
+//! }
+//!
+//! let mut vec = vec![0, 1, 2, 3, 4, 5];
+//! let drained: Vec<_> = vec.drain(Span { lo: 1, hi: 4 }).collect();
+//! assert_eq!(drained, vec![1, 2, 3]);
+//! assert_eq!(vec, vec![0, 4, 5]);
+//! assert!(Span { lo: 'a', hi: 'f' }.contains(&'c'));
+//! ```
+//!
+//! ## `PartialEq`
+//!
+//! `PartialEq { ... }` compares with `Self`, `PartialEq<Rhs> { ... }` with `Rhs`. Either way, `other`
+//! is a *reference* to the right-hand side, so `PartialEq<str>` gets an `other: &str`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Name(String);
+//! implem! {
+//!     impl('a) for Name {
+//!         PartialEq {
+//!             |&self, other| self.0 == other.0
+//!         }
+//!         PartialEq<str> {
+//!             |&self, other| self.0.as_str() == other
+//!         }
+//!         PartialEq<&'a str> {
+//!             |&self, other| self.0.as_str() == *other
+//!         }
+//!         PartialEq<String> {
+//!             |&self, other| &self.0 == other
+//!         }
+//!     }
+//! }
+//!
+//! let name = Name("hello".into());
+//! assert!(name == Name("hello".into()));
+//! assert!(name != Name("world".into()));
+//! assert!(name == "hello");
+//! assert!(name == *"hello");
+//! assert!(name == String::from("hello"));
+//! ```
+//!
+//! ## `Comparable`
+//!
+//! Implements `PartialEq`, `Eq`, `PartialOrd` and `Ord` consistently, by comparing the keys returned
+//! by the closure with the key type's `Ord`. The key type is given as `Key`, and can borrow from
+//! `self`, as in `Comparable<Key = &str> { |&self| &self.name }`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::collections::BTreeSet;
+//!
+//! #[derive(Debug)]
+//! pub struct Task {
+//!     priority: u32,
+//!     name: &'static str,
+//! }
+//! implem! {
+//!     for Task {
+//!         Comparable<Key = (u32, &'static str)> {
+//!             |&self| (self.priority, self.name)
+//!         }
+//!     }
+//! }
+//!
+//! let task = |priority, name| Task { priority, name };
+//! let mut tasks = vec![task(2, "b"), task(1, "z"), task(2, "a")];
+//! tasks.sort();
+//! let names: Vec<_> = tasks.iter().map(|t| t.name).collect();
+//! assert_eq!(names, vec!["z", "a", "b"]);
+//! assert!(task(1, "a") < task(1, "b"));
+//! assert_eq!(task(3, "c"), task(3, "c"));
+//!
+//! let set: BTreeSet<Task> = tasks.into_iter().chain(Some(task(1, "z"))).collect();
+//! assert_eq!(set.len(), 3);
+//! ```
+//!
+//! ## `Hashable`
+//!
+//! Implements `Hash`, `PartialEq` and `Eq` consistently, through the key returned by the closure.
+//! As for `Comparable`, the key type is given as `Key`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::collections::HashMap;
+//!
+//! pub struct User {
+//!     id: u64,
+//!     last_seen: u64,
+//! }
+//! implem! {
+//!     for User {
+//!         Hashable<Key = u64> {
+//!             |&self| self.id
+//!         }
+//!     }
+//! }
+//!
+//! let mut visits = HashMap::new();
+//! *visits.entry(User { id: 1, last_seen: 10 }).or_insert(0) += 1;
+//! *visits.entry(User { id: 2, last_seen: 10 }).or_insert(0) += 1;
+//! *visits.entry(User { id: 1, last_seen: 42 }).or_insert(0) += 1;
+//! assert_eq!(visits.len(), 2);
+//! assert_eq!(visits[&User { id: 1, last_seen: 0 }], 2);
+//! ```
+//!
+//! `Comparable` and `Hashable` both implement `PartialEq` and `Eq`, so a type cannot use both
+//! (`E0119`, conflicting implementations). `Comparable + Hashable` generates the five impls from a
+//! single key instead, for types that are both ordered and hashed.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::collections::{BTreeSet, HashSet};
+//!
+//! pub struct Tag {
+//!     name: String,
+//!     uses: usize,
+//! }
+//! implem! {
+//!     for Tag {
+//!         Comparable + Hashable<Key = &str> {
+//!             |&self| &self.name
+//!         }
+//!     }
+//! }
+//!
+//! let tag = |name: &str, uses| Tag { name: name.into(), uses };
+//! let sorted: BTreeSet<_> = vec![tag("b", 1), tag("a", 2), tag("b", 3)].into_iter().collect();
+//! let names: Vec<_> = sorted.iter().map(|t| t.name.as_str()).collect();
+//! assert_eq!(names, vec!["a", "b"]);
+//! let hashed: HashSet<_> = vec![tag("b", 1), tag("a", 2), tag("b", 3)].into_iter().collect();
+//! assert_eq!(hashed.len(), 2);
+//! assert!(hashed.contains(&tag("a", 0)));
+//! ```
+//!
+//! ## `Hasher`
+//!
+//! Takes two closures, for `write` and `finish` respectively.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::hash::Hasher;
+//!
+//! pub struct Fnv {
+//!     state: u64,
+//! }
+//! implem! {
+//!     for Fnv {
+//!         Hasher {
+//!             |&mut self, bytes| for byte in bytes {
+//!                 self.state ^= u64::from(*byte);
+//!                 self.state = self.state.wrapping_mul(0x100000001b3);
+//!             },
+//!             |&self| self.state,
+//!         }
+//!     }
+//! }
+//!
+//! let hash = |bytes: &[u8]| {
+//!     let mut hasher = Fnv { state: 0xcbf29ce484222325 };
+//!     hasher.write(bytes);
+//!     hasher.finish()
+//! };
+//! assert_eq!(hash(b"cat"), hash(b"cat"));
+//! assert_ne!(hash(b"cat"), hash(b"dog"));
+//!
+//! // Works through `Hash::hash` too.
+//! use std::hash::Hash;
+//! let hash_value = |value: &(&str, u32)| {
+//!     let mut hasher = Fnv { state: 0xcbf29ce484222325 };
+//!     value.hash(&mut hasher);
+//!     hasher.finish()
+//! };
+//! assert_eq!(hash_value(&("cat", 7)), hash_value(&("cat", 7)));
+//! assert_ne!(hash_value(&("cat", 7)), hash_value(&("cat", 8)));
+//! ```
+//!
+//! ## `BuildHasher`
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::collections::HashMap;
+//!
+//! pub struct DetHasher {
+//!     state: u64,
+//! }
+//! implem! {
+//!     for DetHasher {
+//!         Hasher {
+//!             |&mut self, bytes| for byte in bytes {
+//!                 self.state = (self.state ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+//!             },
+//!             |&self| self.state,
+//!         }
+//!     }
+//! }
+//!
+//! pub struct Seeded {
+//!     seed: u64,
+//! }
+//! implem! {
+//!     for Seeded {
+//!         BuildHasher<Hasher = DetHasher> {
+//!             |&self| DetHasher { state: self.seed }
+//!         }
+//!     }
+//! }
+//!
+//! let build = || {
+//!     let mut map = HashMap::with_hasher(Seeded { seed: 0xcbf29ce484222325 });
+//!     for (i, word) in ["cat", "dog", "bird", "fish", "ant"].iter().enumerate() {
+//!         map.insert(*word, i);
+//!     }
+//!     map.into_iter().collect::<Vec<_>>()
+//! };
+//! assert_eq!(build(), build());
+//! ```
+//!
+//! ## `Iterator`
+//!
+//! Methods are given as labeled closures, in any order. `next` is required, `size_hint`, `nth` and
+//! `fold` override the default implementations when present.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Countdown {
+//!     left: usize,
+//! }
+//! implem! {
+//!     for Countdown {
+//!         Iterator<Item = usize> {
+//!             next: |&mut self| {
+//!                 self.left = self.left.checked_sub(1)?;
+//!                 Some(self.left)
+//!             },
+//!             size_hint: |&self| (self.left, Some(self.left)),
+//!             nth: |&mut self, n| {
+//!                 self.left = self.left.checked_sub(n)?;
+//!                 self.next()
+//!             },
+//!             fold: |self, init, f| (0..self.left).rev().fold(init, f),
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(Countdown { left: 3 }.size_hint(), (3, Some(3)));
+//! assert_eq!(Countdown { left: 10 }.nth(2), Some(7));
+//! assert_eq!(Countdown { left: 10 }.nth(10), None);
+//! assert_eq!(Countdown { left: 4 }.fold(0, |acc, n| acc * 10 + n), 3210);
+//!
+//! // `collect` allocates once thanks to the exact `size_hint`.
+//! let all: Vec<_> = Countdown { left: 100 }.collect();
+//! assert_eq!(all.len(), 100);
+//! assert_eq!(all.capacity(), 100);
+//! ```
+//!
+//! Unknown labels are rejected.
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! pub struct Countdown {
+//!     left: usize,
+//! }
+//! implem! {
+//!     for Countdown {
+//!         Iterator<Item = usize> {
+//!             next: |&mut self| None,
+//!             last: |self| None,
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ## `FusedIterator`
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::iter::FusedIterator;
+//!
+//! pub struct Countdown(u32);
+//! impl Iterator for Countdown {
+//!     type Item = u32;
+//!     fn next(&mut self) -> Option<u32> {
+//!         let current = self.0.checked_sub(1)?;
+//!         self.0 = current;
+//!         Some(current)
+//!     }
+//! }
+//! implem! {
+//!     for Countdown {
+//!         FusedIterator {}
+//!     }
+//! }
+//!
+//! fn requires_fused<I: FusedIterator>(iter: I) -> I {
+//!     iter
+//! }
+//!
+//! let mut fused = requires_fused(Countdown(2)).fuse();
+//! assert_eq!(fused.next(), Some(1));
+//! assert_eq!(fused.next(), Some(0));
+//! assert_eq!(fused.next(), None);
+//! assert_eq!(fused.next(), None);
+//! ```
+//!
+//! ## `Read`
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::io::{Cursor, Read};
+//!
+//! pub struct Counting<R> {
+//!     inner: R,
+//!     bytes_in: u64,
+//! }
+//! implem! {
+//!     impl(R) for Counting<R> where (R: Read) {
+//!         Read {
+//!             |&mut self, buf| {
+//!                 let n = self.inner.read(buf)?;
+//!                 self.bytes_in += n as u64;
+//!                 Ok(n)
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let mut reader = Counting { inner: Cursor::new(b"hello world"), bytes_in: 0 };
+//! let mut s = String::new();
+//! reader.read_to_string(&mut s).unwrap();
+//! assert_eq!(s, "hello world");
+//! assert_eq!(reader.bytes_in, 11);
+//! ```
+//!
+//! ## `Write`
+//!
+//! Implements [`std::io::Write`] with two closures, for `write` and `flush` respectively. A third
+//! *optional* closure overrides `write_all`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::io::Write;
+//!
+//! pub struct Buffered<W> {
+//!     inner: W,
+//!     flushes: usize,
+//! }
+//! implem! {
+//!     impl(W) for Buffered<W> where (W: Write) {
+//!         Write {
+//!             |&mut self, buf| self.inner.write(buf),
+//!             |&mut self| {
+//!                 self.flushes += 1;
+//!                 self.inner.flush()
+//!             },
+//!         }
+//!     }
+//! }
+//!
+//! let mut writer = Buffered { inner: Vec::new(), flushes: 0 };
+//! write!(writer, "{} dogs", 2).unwrap();
+//! writer.flush().unwrap();
+//! assert_eq!(writer.inner, b"2 dogs");
+//! assert_eq!(writer.flushes, 1);
+//! ```
+//!
+//! ## `Seek`
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+//!
+//! pub struct Tracked<T> {
+//!     inner: T,
+//!     pos: u64,
+//! }
+//! implem! {
+//!     impl(T) for Tracked<T> where (T: Read + Write + Seek) {
+//!         Read {
+//!             |&mut self, buf| {
+//!                 let n = self.inner.read(buf)?;
+//!                 self.pos += n as u64;
+//!                 Ok(n)
+//!             }
+//!         }
+//!         Write {
+//!             |&mut self, buf| {
+//!                 let n = self.inner.write(buf)?;
+//!                 self.pos += n as u64;
+//!                 Ok(n)
+//!             },
+//!             |&mut self| self.inner.flush(),
+//!         }
+//!         Seek {
+//!             |&mut self, pos| {
+//!                 self.pos = self.inner.seek(pos)?;
+//!                 Ok(self.pos)
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let mut tracked = Tracked { inner: Cursor::new(Vec::new()), pos: 0 };
+//! tracked.write_all(b"hello world").unwrap();
+//! assert_eq!(tracked.pos, 11);
+//! tracked.seek(SeekFrom::Start(6)).unwrap();
+//! assert_eq!(tracked.pos, 6);
+//! let mut s = String::new();
+//! tracked.read_to_string(&mut s).unwrap();
+//! assert_eq!(s, "world");
+//! assert_eq!(tracked.pos, tracked.inner.position());
+//! ```
+//!
+//! `IoSeek` is an alias, for symmetry with `FmtWrite` or to make the `std::io` origin explicit.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::io::{Cursor, Read, Seek, SeekFrom};
+//!
+//! /// Only exposes the bytes after a header.
+//! pub struct Body<T> {
+//!     inner: T,
+//!     header: u64,
+//! }
+//! implem! {
+//!     impl(T) for Body<T> where (T: Seek) {
+//!         IoSeek {
+//!             |&mut self, pos| {
+//!                 let pos = match pos {
+//!                     SeekFrom::Start(n) => SeekFrom::Start(self.header + n),
+//!                     relative => relative,
+//!                 };
+//!                 Ok(self.inner.seek(pos)? - self.header)
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let mut body = Body { inner: Cursor::new(b"HDR:0123456789".to_vec()), header: 4 };
+//! assert_eq!(body.seek(SeekFrom::Start(2)).unwrap(), 2);
+//! assert_eq!(body.seek(SeekFrom::Current(3)).unwrap(), 5);
+//! assert_eq!(body.seek(SeekFrom::End(-1)).unwrap(), 9);
+//! let mut last = String::new();
+//! body.inner.read_to_string(&mut last).unwrap();
+//! assert_eq!(last, "9");
+//! ```
+//!
+//! ## `BufRead`
+//!
+//! Takes two closures, for `fill_buf` and `consume` respectively. `BufRead` requires `Read`, which the
+//! compiler checks as usual.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::io::{BufRead, BufReader, Read};
+//!
+//! pub struct Lines<R> {
+//!     inner: BufReader<R>,
+//!     consumed: usize,
+//! }
+//! implem! {
+//!     impl(R) for Lines<R> where (R: Read) {
+//!         Read {
+//!             |&mut self, buf| self.inner.read(buf)
+//!         }
+//!         BufRead {
+//!             |&mut self| self.inner.fill_buf(),
+//!             |&mut self, amt| {
+//!                 self.consumed += amt;
+//!                 self.inner.consume(amt)
+//!             },
+//!         }
+//!     }
+//! }
+//!
+//! let mut reader = Lines { inner: BufReader::new(&b"one\ntwo\nthree"[..]), consumed: 0 };
+//! let lines: Vec<String> = (&mut reader).lines().map(Result::unwrap).collect();
+//! assert_eq!(lines, vec!["one", "two", "three"]);
+//! assert_eq!(reader.consumed, 13);
+//! ```
+//!
+//! `IoBufRead` is an alias, like `IoSeek`. Here the type owns its buffer:
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::io::{BufRead, Read};
+//!
+//! pub struct Buffered {
+//!     buf: Vec<u8>,
+//!     consumed: usize,
+//! }
+//! implem! {
+//!     for Buffered {
+//!         Read {
+//!             |&mut self, out| {
+//!                 let n = self.fill_buf()?.read(out)?;
+//!                 self.consume(n);
+//!                 Ok(n)
+//!             }
+//!         }
+//!         IoBufRead {
+//!             |&mut self| Ok(&self.buf[self.consumed..]),
+//!             |&mut self, amt| self.consumed = (self.consumed + amt).min(self.buf.len()),
+//!         }
+//!     }
+//! }
+//!
+//! let reader = Buffered { buf: b"a\nbb\n\nccc".to_vec(), consumed: 0 };
+//! let lines: Vec<String> = reader.lines().map(Result::unwrap).collect();
+//! assert_eq!(lines, vec!["a", "bb", "", "ccc"]);
+//! ```
+//!
+//! ## `ToSocketAddrs`
+//!
+//! The body can use `?` on `std::io::Result`s.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+//!
+//! pub struct Config {
+//!     host: String,
+//!     port: u16,
+//! }
+//! implem! {
+//!     for Config {
+//!         ToSocketAddrs<Iter = std::vec::IntoIter<SocketAddr>> {
+//!             |&self| {
+//!                 let ip: IpAddr = self.host.parse().map_err(|e| {
+//!                     std::io::Error::new(std::io::ErrorKind::InvalidInput, e)
+//!                 })?;
+//!                 Ok(vec![SocketAddr::new(ip, self.port)].into_iter())
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let config = Config { host: "127.0.0.1".into(), port: 8080 };
+//! let addrs: Vec<_> = config.to_socket_addrs().unwrap().collect();
+//! assert_eq!(addrs, vec!["127.0.0.1:8080".parse().unwrap()]);
+//! let bad = Config { host: "not an ip".into(), port: 8080 };
+//! assert!(bad.to_socket_addrs().is_err());
+//! ```
+//!
+//! ## `AsRawFd` and `AsFd`
+//!
+//! The impls are generated under `#[cfg(unix)]`, so the rest of the block still compiles on other
+//! platforms. Code that *uses* them, like the `std::os::fd` imports below, needs the same gating.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::net::TcpListener;
+//!
+//! pub struct Server {
+//!     inner: TcpListener,
+//! }
+//! implem! {
+//!     for Server {
+//!         AsRawFd {
+//!             |&self| std::os::fd::AsRawFd::as_raw_fd(&self.inner)
+//!         }
+//!         AsFd {
+//!             |&self| std::os::fd::AsFd::as_fd(&self.inner)
+//!         }
+//!         Display {
+//!             |&self, fmt| write!(fmt, "server on {:?}", self.inner.local_addr())
+//!         }
+//!     }
+//! }
+//!
+//! # #[cfg(unix)]
+//! # {
+//! use std::os::fd::{AsFd, AsRawFd};
+//!
+//! let server = Server { inner: TcpListener::bind("127.0.0.1:0").unwrap() };
+//! assert_eq!(server.as_raw_fd(), server.inner.as_raw_fd());
+//! assert_eq!(server.as_fd().as_raw_fd(), server.inner.as_raw_fd());
+//! # }
+//! ```
+//!
+//! ## `Future`
+//!
+//! The receiver must be written `self: Pin<&mut Self>`, `Pin` does not need to be in scope.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::{
+//!     future::Future,
+//!     pin::pin,
+//!     task::{Context, Poll, Waker},
+//! };
+//!
+//! pub struct Countdown(u32);
+//! implem! {
+//!     for Countdown {
+//!         Future<Output = &'static str> {
+//!             |self: Pin<&mut Self>, cx| if self.0 == 0 {
+//!                 Poll::Ready("liftoff")
+//!             } else {
+//!                 self.get_mut().0 -= 1;
+//!                 cx.waker().wake_by_ref();
+//!                 Poll::Pending
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! let mut future = pin!(Countdown(2));
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+//! assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+//! assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready("liftoff"));
+//! ```
+//!
+//! Such futures can be `.await`ed like any other, here in an `async` block driven by a minimal
+//! executor:
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::{
+//!     future::Future,
+//!     pin::pin,
+//!     task::{Context, Poll, Waker},
+//! };
+//!
+//! /// Ready on first poll, `Unpin` so `get_mut` is fine.
+//! pub struct Ready<T> {
+//!     value: Option<T>,
+//! }
+//! implem! {
+//!     impl(T) for Ready<T> where (T: Unpin) {
+//!         Future<Output = T> {
+//!             |self: Pin<&mut Self>, _cx| {
+//!                 Poll::Ready(self.get_mut().value.take().expect("polled after completion"))
+//!             }
+//!         }
+//!     }
+//! }
+//!
+//! fn block_on<F: Future>(future: F) -> F::Output {
+//!     let mut future = pin!(future);
+//!     let mut cx = Context::from_waker(Waker::noop());
+//!     loop {
+//!         if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+//!             return output;
+//!         }
+//!     }
+//! }
+//!
+//! let sum = block_on(async {
+//!     let a = Ready { value: Some(20) }.await;
+//!     let b = Ready { value: Some(22) }.await;
+//!     a + b
+//! });
+//! assert_eq!(sum, 42);
+//! ```
+//!
+//! ## `IntoFuture`
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::{
+//!     future::Future,
+//!     pin::{pin, Pin},
+//!     task::{Context, Poll, Waker},
+//! };
+//!
+//! pub struct Request {
+//!     id: u32,
+//! }
+//! impl Request {
+//!     async fn send(self) -> String {
+//!         format!("response #{}", self.id)
+//!     }
+//! }
+//! implem! {
+//!     for Request {
+//!         IntoFuture<Output = String, IntoFuture = Pin<Box<dyn Future<Output = String>>>> {
+//!             |self| Box::pin(self.send())
+//!         }
+//!     }
+//! }
+//!
+//! fn block_on<F: Future>(future: F) -> F::Output {
+//!     let mut future = pin!(future);
+//!     let mut cx = Context::from_waker(Waker::noop());
+//!     loop {
+//!         if let Poll::Ready(res) = future.as_mut().poll(&mut cx) {
+//!             return res;
+//!         }
+//!     }
+//! }
+//!
+//! let response = block_on(async { Request { id: 7 }.await });
+//! assert_eq!(response, "response #7");
+//! ```
+//!
+//! ## `Wake`
+//!
+//! The receivers must be written `self: Arc<Self>` for `wake`, and `self: &Arc<Self>` for the
+//! *optional* `wake_by_ref` closure.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::{
+//!     future::Future,
+//!     pin::pin,
+//!     sync::{
+//!         atomic::{AtomicUsize, Ordering},
+//!         Arc,
+//!     },
+//!     task::{Context, Poll, Waker},
+//! };
+//!
+//! pub struct CountingWaker {
+//!     wakes: AtomicUsize,
+//!     wakes_by_ref: AtomicUsize,
+//! }
+//! implem! {
+//!     for CountingWaker {
+//!         Wake {
+//!             |self: Arc<Self>| {
+//!                 self.wakes.fetch_add(1, Ordering::SeqCst);
+//!             },
+//!             |self: &Arc<Self>| {
+//!                 self.wakes_by_ref.fetch_add(1, Ordering::SeqCst);
+//!             },
+//!         }
+//!     }
+//! }
+//!
+//! let counter = Arc::new(CountingWaker {
+//!     wakes: AtomicUsize::new(0),
+//!     wakes_by_ref: AtomicUsize::new(0),
+//! });
+//! let waker = Waker::from(counter.clone());
+//! let mut cx = Context::from_waker(&waker);
+//!
+//! let mut polled = false;
+//! let mut future = pin!(std::future::poll_fn(|cx| if polled {
+//!     Poll::Ready(())
+//! } else {
+//!     polled = true;
+//!     cx.waker().wake_by_ref();
+//!     Poll::Pending
+//! }));
+//! assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+//! assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(()));
+//! waker.wake();
+//! assert_eq!(counter.wakes_by_ref.load(Ordering::SeqCst), 1);
+//! assert_eq!(counter.wakes.load(Ordering::SeqCst), 1);
+//! ```
+//!
+//! ## `Unpin`
+//!
+//! Marker traits take an empty body.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::{marker::PhantomPinned, pin::Pin};
+//!
+//! pub struct MyStruct {
+//!     s: String,
+//!     _pinned: PhantomPinned,
+//! }
+//! implem! {
+//!     for MyStruct {
+//!         Unpin {}
+//!     }
+//! }
+//!
+//! let pinned = Pin::new(Box::new(MyStruct {
+//!     s: "cat".into(),
+//!     _pinned: PhantomPinned,
+//! }));
+//! // only legal because `MyStruct: Unpin`
+//! let val = *Pin::into_inner(pinned);
+//! assert_eq!(val.s, "cat");
+//! ```
+//!
+//! ## `unsafe Send`
+//!
+//! Implementing `Send` is `unsafe`, the macro insists on an explicit `unsafe` token so that the
+//! assertion is visible.
+//!
+//! ```rust
+//! # use implem::implem;
+//! pub struct Handle {
+//!     ptr: *mut u8,
+//! }
+//! implem! {
+//!     for Handle {
+//!         unsafe Send {}
+//!     }
+//! }
+//!
+//! pub struct Wrapper<T> {
+//!     inner: T,
+//!     ptr: *const T,
+//! }
+//! implem! {
+//!     impl(T) for Wrapper<T> where (T: Send) {
+//!         unsafe Send {}
+//!     }
+//! }
+//!
+//! fn requires_send<T: Send>() {}
+//! requires_send::<Handle>();
+//! requires_send::<Wrapper<String>>();
+//! ```
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! pub struct Handle {
+//!     ptr: *mut u8,
+//! }
+//! implem! {
+//!     for Handle {
+//!         // error: missing `unsafe`
+//!         Send {}
+//!     }
+//! }
+//! ```
+//!
+//! ## `unsafe Sync`
+//!
+//! Same as `unsafe Send`, the `unsafe` token is mandatory.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::{
+//!     cell::UnsafeCell,
+//!     sync::atomic::{AtomicBool, Ordering},
+//! };
+//!
+//! pub struct Cell<T> {
+//!     locked: AtomicBool,
+//!     value: UnsafeCell<T>,
+//! }
+//! impl<T> Cell<T> {
+//!     fn update(&self, f: impl FnOnce(&mut T)) {
+//!         while self.locked.swap(true, Ordering::Acquire) {}
+//!         f(unsafe { &mut *self.value.get() });
+//!         self.locked.store(false, Ordering::Release);
+//!     }
+//! }
+//! implem! {
+//!     impl(T) for Cell<T> where (T: Send) {
+//!         unsafe Sync {}
+//!     }
+//! }
+//!
+//! let cell = Cell { locked: AtomicBool::new(false), value: UnsafeCell::new(0) };
+//! std::thread::scope(|scope| {
+//!     for _ in 0..4 {
+//!         scope.spawn(|| cell.update(|n| *n += 1));
+//!     }
+//! });
+//! assert_eq!(cell.value.into_inner(), 4);
+//! ```
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! pub struct Handle {
+//!     ptr: *mut u8,
+//! }
+//! implem! {
+//!     for Handle {
+//!         // error: missing `unsafe`
+//!         Sync {}
+//!     }
+//! }
+//! ```
+//!
+//! ## `UnwindSafe` and `RefUnwindSafe`
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::{cell::Cell, panic};
+//!
+//! pub struct Counter {
+//!     count: Cell<u32>,
+//! }
+//! implem! {
+//!     for Counter {
+//!         UnwindSafe {}
+//!         RefUnwindSafe {}
+//!     }
+//! }
+//!
+//! let counter = Counter { count: Cell::new(0) };
+//! // `&Counter: UnwindSafe` because `Counter: RefUnwindSafe`
+//! let res = panic::catch_unwind(|| {
+//!     counter.count.set(counter.count.get() + 1);
+//!     counter.count.get()
+//! });
+//! assert_eq!(res.ok(), Some(1));
+//! ```
+//!
+//! ## `Termination`
+//!
+//! Consumes `self`, like `Into`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::process::{ExitCode, Termination};
+//!
+//! pub struct Status {
+//!     code: u8,
+//! }
+//! implem! {
+//!     for Status {
+//!         Termination {
+//!             |self| ExitCode::from(self.code)
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(Status { code: 3 }.report(), ExitCode::from(3));
+//! assert_eq!(Status { code: 0 }.report(), ExitCode::SUCCESS);
+//! ```
+//!
+//! ```rust
+//! # use implem::implem;
+//! # use std::process::ExitCode;
+//! # pub struct Status {
+//! #     code: u8,
+//! # }
+//! # implem! {
+//! #     for Status {
+//! #         Termination {
+//! #             |self| ExitCode::from(self.code)
+//! #         }
+//! #     }
+//! # }
+//! fn main() -> Status {
+//!     Status { code: 0 }
+//! }
+//! ```
+//!
+//! ## `unsafe GlobalAlloc`
+//!
+//! Takes closures for `alloc` and `dealloc`, then optionally for `realloc` and `alloc_zeroed` in that
+//! order. The generated methods are `unsafe fn`s, and the `unsafe` token is mandatory as for
+//! `unsafe Send`.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::alloc::System;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! pub struct Counting {
+//!     allocs: AtomicUsize,
+//!     reallocs: AtomicUsize,
+//! }
+//! implem! {
+//!     for Counting {
+//!         unsafe GlobalAlloc {
+//!             |&self, layout| {
+//!                 self.allocs.fetch_add(1, Ordering::Relaxed);
+//!                 unsafe { System.alloc(layout) }
+//!             },
+//!             |&self, ptr, layout| unsafe { System.dealloc(ptr, layout) },
+//!             |&self, ptr, layout, new_size| {
+//!                 self.reallocs.fetch_add(1, Ordering::Relaxed);
+//!                 unsafe { System.realloc(ptr, layout, new_size) }
+//!             },
+//!         }
+//!     }
+//! }
+//!
+//! #[global_allocator]
+//! static COUNTING: Counting = Counting {
+//!     allocs: AtomicUsize::new(0),
+//!     reallocs: AtomicUsize::new(0),
+//! };
+//!
+//! let allocs = COUNTING.allocs.load(Ordering::Relaxed);
+//! let mut v = Vec::with_capacity(1);
+//! v.push(1u64);
+//! assert!(COUNTING.allocs.load(Ordering::Relaxed) > allocs);
+//! let reallocs = COUNTING.reallocs.load(Ordering::Relaxed);
+//! v.extend(0..1000);
+//! assert!(COUNTING.reallocs.load(Ordering::Relaxed) > reallocs);
+//! ```
+//!
+//! ```rust,compile_fail
+//! # use implem::implem;
+//! use std::alloc::System;
+//!
+//! pub struct Passthrough;
+//! implem! {
+//!     for Passthrough {
+//!         GlobalAlloc {
+//!             |&self, layout| unsafe { System.alloc(layout) },
+//!             |&self, ptr, layout| unsafe { System.dealloc(ptr, layout) },
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ## `Transparent`
+//!
+//! `Transparent { Inner }` generates the impls expected from a tuple newtype around `Inner`, all
+//! delegating to the field: `Display`, `Debug`, `Deref<Target = Inner>`, `DerefMut`, `From<Inner>`,
+//! `AsRef<Inner>`, `Borrow<Inner>`, `PartialEq`, `Eq`, `Hash`, `Clone` and `Default`. It is a bundle
+//! of other definitions, so `Inner` must implement the corresponding traits.
+//!
+//! `Transparent { Inner, Borrowed }` also generates `From<&Borrowed>`, `AsRef<Borrowed>` and
+//! `Borrow<Borrowed>`, and `Transparent { String }` is short for `Transparent { String, str }`. Since
+//! `Hash` and `Eq` are the ones of the field, the newtype works as a map key looked up by `&str`.
+//! Unlike `impl_newtype!`, `Debug` prints the field only, no `Into` is generated, and the block can
+//! have type parameters.
+//!
+//! ```rust
+//! # use implem::implem;
+//! use std::borrow::Borrow;
+//! use std::collections::HashMap;
+//!
+//! pub struct Name(String);
+//! implem! {
+//!     for Name {
+//!         Transparent { String }
+//!     }
+//! }
+//!
+//! let mut name = Name::from("ada");
+//! assert_eq!(name.to_string(), "ada");
+//! assert_eq!(format!("{:?}", name), "\"ada\"");
+//! assert_eq!(name.len(), 3);
+//! name.push_str(" lovelace");
+//! assert_eq!(name, Name::from(String::from("ada lovelace")));
+//! let as_ref: &str = name.as_ref();
+//! let borrowed: &String = name.borrow();
+//! assert_eq!(as_ref, borrowed);
+//! assert_eq!(name.clone(), name);
+//! assert_eq!(Name::default(), Name::from(""));
+//!
+//! let mut ages = HashMap::new();
+//! ages.insert(Name::from("grace"), 85);
+//! assert_eq!(ages.get("grace"), Some(&85));
+//!
+//! pub struct Id<T>(T);
+//! implem! {
+//!     impl(T) for Id<T>
+//!     where (T: std::fmt::Display + std::fmt::Debug + Clone + Eq + std::hash::Hash + Default) {
+//!         Transparent { T }
+//!     }
+//! }
+//! let id = Id::from(7u64);
+//! assert_eq!(id.to_string(), "7");
+//! assert_eq!(*id + 1, 8);
+//! assert_eq!(Id::<u64>::default(), Id::from(0));
+//! ```
+//!
+//! ## `const` impls (`nightly`)
+//!
+//! `From`, `Into`, `Deref`, `DerefMut` and `Default` accept a `const` qualifier, as in
+//! `const From<u32> { |n| Self(n) }`, which generates `impl const From<u32> for ...`. Const trait
+//! impls are unstable: they require the `nightly` feature, and the crate using them must enable
+//! `#![feature(const_trait_impl)]` plus the relevant `const_convert`/`const_default` features. See
+//! `examples/const_impls.rs`.
+//!
+//! ## `FnOnce`, `FnMut` and `Fn` (`nightly`)
+//!
+//! The crate using them must enable `#![feature(fn_traits, unboxed_closures)]`. The arguments are
+//! given as a tuple type and destructured by a tuple pattern. As in the standard library, `FnMut`
+//! requires `FnOnce` and `Fn` requires `FnMut`.
+//!
+//! ```text
+//! FnOnce<(i32, i32), Output = i32> { |self, (a, b)| self.offset + a + b }
+//! FnMut<(i32, i32)> { |&mut self, (a, b)| self.offset + a + b }
+//! Fn<(i32, i32)> { |&self, (a, b)| self.offset + a + b }
+//! ```
+//!
+//! See `examples/fn_traits.rs`.
+//!
+//! ## `Try` and `FromResidual` (`nightly`)
+//!
+//! The crate using them must enable `#![feature(try_trait_v2)]`. `Try` takes closures for
+//! `from_output` and `branch`, `FromResidual` one for `from_residual`; its type parameter defaults
+//! to the `Residual` of the `Try` impl. Recent nightlies also need the residual type to implement
+//! `std::ops::Residual` (`try_trait_v2_residual`), which a plain
+//! `impl std::ops::Residual<T> { ... }` definition handles.
+//!
+//! ```text
+//! Try<Output = T, Residual = Fallible<Infallible>> {
+//!     |value| Fallible::Value(value),
+//!     |self| match self { ... },
+//! }
+//! FromResidual { |residual| match residual { ... } }
+//! ```
+//!
+//! See `examples/try_trait.rs`.
+//!
+//! ## `Step` (`nightly`)
+//!
+//! The crate using it must enable `#![feature(step_trait)]`. Makes ranges of the type iterable.
+//! Methods are labeled closures in any order, the required ones being `steps_between`,
+//! `forward_checked` and `backward_checked`; the type must also be `Clone` and `PartialOrd`.
+//!
+//! ```text
+//! Step {
+//!     steps_between: |start, end| u64::steps_between(&start.0, &end.0),
+//!     forward_checked: |start, count| u64::forward_checked(start.0, count).map(UserId),
+//!     backward_checked: |start, count| u64::backward_checked(start.0, count).map(UserId),
+//! }
+//! ```
+//!
+//! See `examples/step_trait.rs`.
+//!
+//! ## `Serialize` and `Deserialize` (`serde`)
+//!
+//! The closure's `serializer` is a generic `S: serde::Serializer` introduced by the macro.
+//!
+//! `Deserialize` similarly introduces the `'de` lifetime and a `D: serde::Deserializer<'de>` for
+//! `deserializer`. The `'de` lifetime can be used in the where clause, typically to borrow from
+//! the input.
+//!
+//! ```text
+//! for Id {
+//!     Serialize { |&self, serializer| serializer.serialize_str(&self.to_string()) }
+//!     Deserialize {
+//!         |deserializer| {
+//!             let s = String::deserialize(deserializer)?;
+//!             s.parse().map_err(serde::de::Error::custom)
+//!         }
+//!     }
+//! }
+//! impl('a) for Name<'a> where ('de: 'a) {
+//!     Deserialize { |deserializer| <&'de str>::deserialize(deserializer).map(Name) }
+//! }
+//! ```
+//!
+//! `SerializeViaDisplay {}` serializes as the `Display` string, `DeserializeViaFromStr {}` parses
+//! with `FromStr`, turning parse errors into `serde::de::Error::custom`. `SerdeViaStr {}` does
+//! both, and works for map keys too.
+//!
+//! ```text
+//! for Version {
+//!     DisplayFromStr { |&self, fmt| ..., |s| ... }
+//!     SerdeViaStr {}
+//! }
+//! ```
+//!
+//! ## `Stream` and `Sink` (`futures`)
+//!
+//! As for `Future`, the receiver of `Stream` is written `self: Pin<&mut Self>`; an optional second
+//! closure implements `size_hint`.
+//!
+//! ```text
+//! Stream<Item = u32> {
+//!     |self: Pin<&mut Self>, cx| Poll::Ready(self.get_mut().next()),
+//!     |&self| (self.left, Some(self.left)),
+//! }
+//! ```
+//!
+//! `Sink<Item, Error = E>` takes its four methods as labeled closures, in any order.
+//!
+//! ```text
+//! Sink<String, Error = io::Error> {
+//!     poll_ready: |self: Pin<&mut Self>, cx| Poll::Ready(Ok(())),
+//!     start_send: |self: Pin<&mut Self>, item| Ok(self.get_mut().batch.push(item)),
+//!     poll_flush: |self: Pin<&mut Self>, cx| self.get_mut().write_batch(),
+//!     poll_close: |self: Pin<&mut Self>, cx| self.poll_flush(cx),
+//! }
+//! ```
+//!
+//! ## `AsyncRead` and `AsyncWrite` (`tokio-io`)
+//!
+//! Receivers are written `self: Pin<&mut Self>`, `buf` is a `&mut tokio::io::ReadBuf<'_>` for
+//! `AsyncRead` and a `&[u8]` for `poll_write`. `AsyncWrite` takes its three methods as labeled
+//! closures, in any order.
+//!
+//! ```text
+//! AsyncRead {
+//!     |self: Pin<&mut Self>, cx, buf| Pin::new(&mut self.get_mut().inner).poll_read(cx, buf)
+//! }
+//! AsyncWrite {
+//!     poll_write: |self: Pin<&mut Self>, cx, buf| {
+//!         let this = self.get_mut();
+//!         this.written += buf.len();
+//!         Pin::new(&mut this.inner).poll_write(cx, buf)
+//!     },
+//!     poll_flush: |self: Pin<&mut Self>, cx| Pin::new(&mut self.get_mut().inner).poll_flush(cx),
+//!     poll_shutdown: |self: Pin<&mut Self>, cx| Pin::new(&mut self.get_mut().inner).poll_shutdown(cx),
+//! }
+//! ```
+//!
+//! ## `IntoParallelIterator` (`rayon`)
+//!
+//! Both associated types are given in the header, reference self types work as with any other
+//! trait.
+//!
+//! ```text
+//! use rayon::prelude::*;
+//!
+//! pub struct Bag {
+//!     items: Vec<u64>,
+//! }
+//! implem! {
+//!     for Bag {
+//!         IntoParallelIterator<Item = u64, Iter = rayon::vec::IntoIter<u64>> {
+//!             |self| self.items.into_par_iter()
+//!         }
+//!     }
+//!     impl('a) for &'a Bag {
+//!         IntoParallelIterator<Item = &'a u64, Iter = rayon::slice::Iter<'a, u64>> {
+//!             |self| self.items.par_iter()
+//!         }
+//!     }
+//! }
+//!
+//! let bag = Bag { items: (1..=100).collect() };
+//! let sequential: u64 = bag.items.iter().sum();
+//! assert_eq!((&bag).into_par_iter().sum::<u64>(), sequential);
+//! assert_eq!(bag.into_par_iter().sum::<u64>(), sequential);
+//! ```
+//!
+//! ## `Zero` and `One` (`num-traits`)
+//!
+//! `Zero` takes the constructor and `is_zero`, `One` only the constructor. Their `Add` and `Mul`
+//! supertraits can be defined in the same block.
+//!
+//! ```text
+//! use num_traits::{One, Zero};
+//! use std::ops::Add;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! pub struct Meters(u32);
+//! implem! {
+//!     for Meters {
+//!         Add<Output = Self> {
+//!             |self, rhs| Self(self.0 + rhs.0)
+//!         }
+//!         Mul<Output = Self> {
+//!             |self, rhs| Self(self.0 * rhs.0)
+//!         }
+//!         Zero {
+//!             || Self(0),
+//!             |&self| self.0 == 0
+//!         }
+//!         One {
+//!             || Self(1)
+//!         }
+//!     }
+//! }
+//!
+//! fn sum_all<T: Zero + Add<Output = T>>(items: impl IntoIterator<Item = T>) -> T {
+//!     items.into_iter().fold(T::zero(), |acc, n| acc + n)
+//! }
+//! assert_eq!(sum_all([Meters(1), Meters(2), Meters(3)]), Meters(6));
+//! assert!(sum_all(Vec::<Meters>::new()).is_zero());
+//! assert!(Meters::one().is_one());
+//! ```
+//!
+//! ## `FromPrimitive` and `ToPrimitive` (`num-traits`)
+//!
+//! Methods are labeled closures in any order: `from_i64` and `from_u64` (resp. `to_i64` and
+//! `to_u64`) are required, `from_f64` (resp. `to_f64`) is optional. The other methods keep their
+//! default definitions.
+//!
+//! ```text
+//! use num_traits::{FromPrimitive, ToPrimitive};
+//! use std::convert::TryFrom;
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! pub enum Level {
+//!     Low = 0,
+//!     High = 1,
+//! }
+//! implem! {
+//!     for Level {
+//!         FromPrimitive {
+//!             from_i64: |n| u64::try_from(n).ok().and_then(Self::from_u64),
+//!             from_u64: |n| match n {
+//!                 0 => Some(Self::Low),
+//!                 1 => Some(Self::High),
+//!                 _ => None,
+//!             },
+//!         }
+//!         ToPrimitive {
+//!             to_i64: |&self| Some(*self as i64),
+//!             to_u64: |&self| Some(*self as u64),
+//!         }
+//!     }
+//! }
+//!
+//! assert_eq!(Level::from_u64(1), Some(Level::High));
+//! assert_eq!(Level::from_u64(2), None);
+//! assert_eq!(Level::from_i64(-1), None);
+//! assert_eq!(Level::High.to_i64(), Some(1));
+//! ```
+//!
+//! ## `Arbitrary` (`quickcheck`)
+//!
+//! Requires the `quickcheck` feature, off by default. The impl refers to `::quickcheck`, so the crate
+//! using it must depend on `quickcheck`. The first closure takes the `&mut quickcheck::Gen` and
+//! defines `arbitrary`, the second optional one defines `shrink`.
+//!
+//! ```text
+//! use quickcheck::{quickcheck, Arbitrary};
+//!
+//! #[derive(Debug, Clone)]
+//! pub struct NonEmpty(String);
+//! impl NonEmpty {
+//!     pub fn new(s: String) -> Option<Self> {
+//!         if s.is_empty() { None } else { Some(Self(s)) }
+//!     }
+//! }
+//! implem! {
+//!     for NonEmpty {
+//!         Default {
+//!             || Self("?".into())
+//!         }
+//!         Arbitrary {
+//!             |g| Self::new(String::arbitrary(g)).unwrap_or_default(),
+//!             |&self| Box::new(self.0.shrink().filter_map(Self::new)),
+//!         }
+//!     }
+//! }
+//!
+//! fn never_empty(s: NonEmpty) -> bool {
+//!     !s.0.is_empty()
+//! }
+//! quickcheck(never_empty as fn(NonEmpty) -> bool);
+//! ```
+//!
+//! ## `ProptestArbitrary` (`proptest`)
+//!
+//! Implements `proptest::arbitrary::Arbitrary`, named `ProptestArbitrary` to leave `Arbitrary` to
+//! `quickcheck`. The closure defines `arbitrary_with` and takes the parameters, `Parameters`
+//! defaults to `()`.
+//!
+//! ```text
+//! use proptest::prelude::*;
+//!
+//! #[derive(Debug)]
+//! pub struct Even(u64);
+//! implem! {
+//!     for Even {
+//!         ProptestArbitrary<Strategy = BoxedStrategy<Self>> {
+//!             |_| any::<u64>().prop_map(|n| Self(n & !1)).boxed()
+//!         }
+//!     }
+//! }
+//!
+//! #[derive(Debug)]
+//! pub struct Below(u64);
+//! implem! {
+//!     for Below {
+//!         ProptestArbitrary<Parameters = u64, Strategy = BoxedStrategy<Self>> {
+//!             |max| (0..max).prop_map(Self).boxed()
+//!         }
+//!     }
+//! }
+//!
+//! proptest! {
+//!     #[test]
+//!     fn even_is_even(n: Even) {
+//!         prop_assert_eq!(n.0 % 2, 0);
+//!     }
+//!     #[test]
+//!     fn below_is_below(n in Below::arbitrary_with(10)) {
+//!         prop_assert!(n.0 < 10);
+//!     }
+//! }
+//! ```
+//!
+//! ## `Arbitrary<'a>` (`arbitrary`)
+//!
+//! Requires the `arbitrary` feature, off by default. The impl refers to `::arbitrary`, so the crate
+//! using it must depend on `arbitrary`. The lifetime of the trait is written in the definition, which
+//! distinguishes it from `quickcheck`'s `Arbitrary`, and is added to the impl's parameters by the
+//! macro: it can appear in where clauses without being listed in `impl(...)`. The first closure takes
+//! the `&mut Unstructured<'a>` and defines `arbitrary`, the second optional one takes the depth and
+//! defines `size_hint`.
+//!
+//! ```text
+//! use arbitrary::{Arbitrary, Unstructured};
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Frame {
+//!     len: u16,
+//!     flags: u8,
+//! }
+//! implem! {
+//!     for Frame {
+//!         Arbitrary<'a> {
+//!             |u| Ok(Self { len: u.arbitrary()?, flags: u.arbitrary()? }),
+//!             |depth| arbitrary::size_hint::and(u16::size_hint(depth), u8::size_hint(depth)),
+//!         }
+//!     }
+//! }
+//!
+//! let mut u = Unstructured::new(&[1, 0, 7]);
+//! assert_eq!(Frame::arbitrary(&mut u).unwrap(), Frame { len: 1, flags: 7 });
+//! assert_eq!(Frame::size_hint(0), (3, Some(3)));
+//! ```
+//!
+//! ## `RandStandard` (`rand`)
+//!
+//! Implements `rand::distributions::Distribution<T> for rand::distributions::Standard`, `T` being
+//! the block's self type, so that `rng.gen::<T>()` works. `rand` 0.9 renamed `Standard` to
+//! `distr::StandardUniform`, which this definition does not target. The closure takes the
+//! `&mut impl Rng` and defines `sample`. Note that `Self` is `Standard` in the body, use the type's
+//! name instead.
+//!
+//! ```text
+//! use rand::Rng;
+//!
+//! #[derive(Debug, Clone, Copy)]
+//! pub struct Angle(f64);
+//! implem! {
+//!     for Angle {
+//!         RandStandard {
+//!             |rng| Angle(rng.gen_range(0.0..360.0))
+//!         }
+//!     }
+//! }
+//!
+//! let mut rng = rand::thread_rng();
+//! for _ in 0..10 {
+//!     let angle: Angle = rng.gen();
+//!     assert!((0.0..360.0).contains(&angle.0));
+//! }
+//! ```
+//!
+//! ## `Buf` and `unsafe BufMut` (`bytes`)
+//!
+//! Methods are labeled closures in any order. `BufMut` is an unsafe trait, and `advance_mut` is
+//! generated as an `unsafe fn`.
+//!
+//! ```text
+//! use bytes::{Buf, BufMut};
+//!
+//! pub struct Frame {
+//!     data: Vec<u8>,
+//!     pos: usize,
+//! }
+//! implem! {
+//!     for Frame {
+//!         Buf {
+//!             remaining: |&self| self.data.len() - self.pos,
+//!             chunk: |&self| &self.data[self.pos..],
+//!             advance: |&mut self, cnt| self.pos += cnt,
+//!         }
+//!     }
+//! }
+//!
+//! let mut frame = Frame { data: vec![0, 0, 0, 1, 0, 0, 1, 0], pos: 0 };
+//! assert_eq!(frame.get_u32(), 1);
+//! assert_eq!(frame.get_u32(), 256);
+//! assert_eq!(frame.remaining(), 0);
+//!
+//! pub struct Out {
+//!     data: Vec<u8>,
+//!     len: usize,
+//! }
+//! implem! {
+//!     for Out {
+//!         unsafe BufMut {
+//!             remaining_mut: |&self| self.data.len() - self.len,
+//!             advance_mut: |&mut self, cnt| self.len += cnt,
+//!             chunk_mut: |&mut self| bytes::buf::UninitSlice::new(&mut self.data[self.len..]),
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ## `ValueEnum` (`clap`)
+//!
+//! Methods are labeled closures in any order, `from_str` keeps its default definition.
+//!
+//! ```text
+//! use clap::{builder::PossibleValue, Arg, Command, ValueEnum};
+//!
+//! #[derive(Debug, Clone, Copy, PartialEq)]
+//! pub enum Speed {
+//!     Fast,
+//!     Slow,
+//! }
+//! implem! {
+//!     for Speed {
+//!         ValueEnum {
+//!             value_variants: || &[Self::Fast, Self::Slow],
+//!             to_possible_value: |&self| Some(PossibleValue::new(match self {
+//!                 Self::Fast => "fast",
+//!                 Self::Slow => "slow",
+//!             })),
+//!         }
+//!     }
+//! }
+//!
+//! let matches = Command::new("run")
+//!     .arg(Arg::new("speed").long("speed").value_parser(clap::value_parser!(Speed)))
+//!     .get_matches_from(["run", "--speed", "slow"]);
+//! assert_eq!(matches.get_one::<Speed>("speed"), Some(&Speed::Slow));
+//! ```
+//!
+//! ## `AbsDiffEq` and `RelativeEq` (`approx`)
+//!
+//! `AbsDiffEq` takes its `Epsilon` type in the header, then both traits take their methods as
+//! labeled closures in any order. `AbsDiffEq` requires `PartialEq`, and `RelativeEq` requires
+//! `AbsDiffEq`.
+//!
+//! ```text
+//! use approx::{assert_abs_diff_eq, assert_relative_eq, AbsDiffEq};
+//!
+//! #[derive(Debug, PartialEq)]
+//! pub struct Meters(f64);
+//! implem! {
+//!     for Meters {
+//!         AbsDiffEq<Epsilon = f64> {
+//!             default_epsilon: || 1e-9,
+//!             abs_diff_eq: |&self, other, eps| self.0.abs_diff_eq(&other.0, eps),
+//!         }
+//!         RelativeEq {
+//!             default_max_relative: || 1e-9,
+//!             relative_eq: |&self, other, eps, max_rel| self.0.relative_eq(&other.0, eps, max_rel),
+//!         }
+//!     }
+//! }
+//!
+//! assert_abs_diff_eq!(Meters(0.1 + 0.2), Meters(0.3));
+//! assert_relative_eq!(Meters(1e6 + 1e-4), Meters(1e6));
+//! ```
+//!
+//! ## `Equivalent` (`equivalent`)
+//!
+//! The block's self type is the *query* type, the key type of the map goes in the header:
+//! `Equivalent<Key>` in a `for Query` block lets `map.get(&query)` look up a `Key`. Keep in mind
+//! the blanket impl for `Eq` queries that keys borrow as, which overlaps with a manual impl.
+//!
+//! ```text
+//! use indexmap::IndexMap;
+//!
+//! #[derive(Debug, PartialEq, Eq, Hash)]
+//! pub struct UserName(String);
+//!
+//! /// Case-insensitive lookup.
+//! pub struct Lookup<'a>(&'a str);
+//! implem! {
+//!     impl('a) for Lookup<'a> {
+//!         Equivalent<UserName> {
+//!             |&self, key| self.0.eq_ignore_ascii_case(&key.0)
+//!         }
+//!     }
+//! }
+//!
+//! let mut ages = IndexMap::new();
+//! ages.insert(UserName("alice".into()), 31);
+//! assert_eq!(ages.get(&Lookup("alice")), Some(&31));
+//! ```
+//!
+//! ## `BorshSerialize` and `BorshDeserialize` (`borsh`)
+//!
+//! The closures take the generic `&mut impl std::io::Write`, resp. the `&mut impl std::io::Read`.
+//! The traits are in scope in their own impl.
+//!
+//! ```text
+//! #[derive(Debug, PartialEq)]
+//! pub struct Lamports(u64);
+//! implem! {
+//!     for Lamports {
+//!         BorshSerialize {
+//!             |&self, writer| self.0.serialize(writer)
+//!         }
+//!         BorshDeserialize {
+//!             |reader| Ok(Self(u64::deserialize_reader(reader)?))
+//!         }
+//!     }
+//! }
+//!
+//! let bytes = borsh::to_vec(&Lamports(42)).unwrap();
+//! assert_eq!(bytes, 42u64.to_le_bytes());
+//! assert_eq!(borsh::from_slice::<Lamports>(&bytes).unwrap(), Lamports(42));
+//! ```
+//!
+//! ## `uDisplay` and `uDebug` (`ufmt`)
+//!
+//! Same closures as `Display` and `Debug`, the formatter being a `&mut ufmt::Formatter<'_, W>` for
+//! some `W: uWrite + ?Sized`.
+//!
+//! ```text
+//! use ufmt::uwrite;
+//!
+//! pub struct Volts(u32);
+//! implem! {
+//!     for Volts {
+//!         Display {
+//!             |&self, fmt| write!(fmt, "{}V", self.0)
+//!         }
+//!         uDisplay {
+//!             |&self, fmt| uwrite!(fmt, "{}V", self.0)
+//!         }
+//!         uDebug {
+//!             |&self, fmt| uwrite!(fmt, "Volts({})", self.0)
+//!         }
+//!     }
+//! }
+//!
+//! let mut buf = heapless::String::<16>::new();
+//! uwrite!(buf, "{}", Volts(5)).unwrap();
+//! assert_eq!(buf.as_str(), Volts(5).to_string());
+//! ```
+//!
+//! ## `Format` (`defmt`)
+//!
+//! The closure gets `&self` and the `defmt::Formatter`, by value, and usually calls
+//! `defmt::write!`.
+//!
+//! ```text
+//! pub struct Temp {
+//!     celsius: f32,
+//! }
+//! implem! {
+//!     for Temp {
+//!         Format {
+//!             |&self, f| defmt::write!(f, "Temp({=f32})", self.celsius)
+//!         }
+//!     }
+//! }
+//!
+//! defmt::info!("reading: {}", Temp { celsius: 21.5 });
+//! ```
+//!
+//! Its tests only check that the expansions compile, running them needs a `defmt` logger.
+//!
+//! ## `Zeroize` and `ZeroizeOnDrop` (`zeroize`)
+//!
+//! `Zeroize` takes a `|&mut self|` closure wiping the value. `ZeroizeOnDrop {}` has no body: it
+//! implements the marker trait along with a `Drop` impl calling `zeroize`, like the derive does.
+//! This `Drop` impl comes with the usual restrictions:
+//!
+//! - the type cannot implement `Drop` itself, both impls would conflict (E0119);
+//! - fields cannot be moved out of the type's values anymore (E0509), for instance by destructuring
+//!   them with `let Secret { bytes } = secret`;
+//! - the block's where clause must match the bounds of the type's declaration (E0367).
+//!
+//! ```text
+//! use zeroize::Zeroize;
+//!
+//! pub struct Secret {
+//!     bytes: Vec<u8>,
+//! }
+//! implem! {
+//!     for Secret {
+//!         Zeroize {
+//!             |&mut self| self.bytes.zeroize()
+//!         }
+//!         ZeroizeOnDrop {}
+//!     }
+//! }
+//!
+//! let mut secret = Secret { bytes: vec![1, 2, 3] };
+//! secret.zeroize();
+//! assert!(secret.bytes.is_empty());
+//! ```
+
+/// The whole point, see [crate-level documentation][doc] for details.
+///
+/// [doc]: ./index.html (crate-level documentation)
+#[macro_export]
+macro_rules! implem {
+    {
+        @checked
+        $(
+            impl ($($t_params:tt)*)
+        )?
+        for $self_ty:ty
+        $(
+            where ($($where_clauses:tt)*)
+        )? {
+            $($stuff:tt)*
+        }
+
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @(
+                $( $($t_params)* )?
+            )(
+                $( $($where_clauses)* )?
+            )(
+                $self_ty
+            )
+            $($stuff)*
+        }
+        $crate::implem! { @checked $($tail)* }
+    };
+    { @checked } => {};
+    { @checked $($stuff:tt)* } => {
+        compile_error! {
+            "expected `$( impl($type_parameters) )? for $type $( where ($where_clauses) )? { ... }`"
+        }
+    };
+    { $($stuff:tt)* } => {
+        $crate::internal! { @check_blocks () $($stuff)* }
+        $crate::implem! { @checked $($stuff)* }
+    };
+}
+
+/// Implements the usual traits of a newtype, a tuple struct with a single field.
+///
+/// `impl_newtype!(Name, Inner)` generates `Display`, `Debug`, `From<Inner>`, `Into<Inner>`,
+/// `Deref<Target = Inner>`, `DerefMut`, `PartialEq`, `Eq`, `Hash` and `Clone`, all going through
+/// the field. Add `skip(...)` to leave some of them out, typically `Eq` and `Hash` for floats.
+/// Further impls can be added with [`implem!`] as usual.
+///
+/// ```rust
+/// # use implem::{implem, impl_newtype};
+/// use std::collections::HashSet;
+///
+/// pub struct UserId(u64);
+/// impl_newtype!(UserId, u64);
+///
+/// let id = UserId::from(7);
+/// assert_eq!(id.to_string(), "7");
+/// assert_eq!(format!("{:?}", id), "UserId(7)");
+/// assert_eq!(*id + 1, 8);
+/// assert!(id.clone() == id);
+/// let ids: HashSet<_> = [UserId(1), UserId(2), UserId(1)].into_iter().collect();
+/// assert_eq!(ids.len(), 2);
+/// let raw: u64 = id.into();
+/// assert_eq!(raw, 7);
+///
+/// pub struct Meters(f64);
+/// impl_newtype!(Meters, f64, skip(Eq, Hash));
+/// implem! {
+///     for Meters {
+///         Default {
+///             || Self(0.0)
+///         }
+///     }
+/// }
+///
+/// let mut length = Meters::default();
+/// *length += 1.5;
+/// assert!(length == Meters(1.5));
+/// assert_eq!(format!("{:.2}", length), "1.50");
+/// ```
+///
+/// Only the traits above can be skipped.
+///
+/// ```rust,compile_fail
+/// # use implem::impl_newtype;
+/// pub struct Meters(f64);
+/// impl_newtype!(Meters, f64, skip(Ord));
+/// ```
+///
+/// The newtype must be a plain identifier: generic newtypes are not supported, as each impl needs
+/// its own bounds on the parameters. Write their impls with [`implem!`] instead.
+///
+/// ```rust,compile_fail
+/// # use implem::impl_newtype;
+/// pub struct Wrapper<T>(T);
+/// impl_newtype!(Wrapper<T>, T);
+/// ```
+#[macro_export]
+macro_rules! impl_newtype {
+    ($name:ident, $inner:ty $(, skip($($skip:ident),* $(,)?))? $(,)?) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            (yes yes yes yes yes yes yes yes yes yes)
+            $($($skip)*)?
+        }
+    };
+    ($name:ident < $($stuff:tt)*) => {
+        compile_error! { concat!(
+            "`impl_newtype!` does not support generic newtypes such as `", stringify!($name),
+            "<...>`, use `implem!` instead"
+        ) }
+    };
+
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+    ) => {
+        $crate::impl_newtype! { @emit($name, $inner) $display Display }
+        $crate::impl_newtype! { @emit($name, $inner) $debug Debug }
+        $crate::impl_newtype! { @emit($name, $inner) $from From }
+        $crate::impl_newtype! { @emit($name, $inner) $into Into }
+        $crate::impl_newtype! { @emit($name, $inner) $deref Deref }
+        $crate::impl_newtype! { @emit($name, $inner) $deref_mut DerefMut }
+        $crate::impl_newtype! { @emit($name, $inner) $partial_eq PartialEq }
+        $crate::impl_newtype! { @emit($name, $inner) $eq Eq }
+        $crate::impl_newtype! { @emit($name, $inner) $hash Hash }
+        $crate::impl_newtype! { @emit($name, $inner) $clone Clone }
+    };
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+        Display $($skip:ident)*
+    ) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            (no $debug $from $into $deref $deref_mut
+                $partial_eq $eq $hash $clone)
+            $($skip)*
+        }
+    };
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+        Debug $($skip:ident)*
+    ) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            ($display no $from $into $deref $deref_mut
+                $partial_eq $eq $hash $clone)
+            $($skip)*
+        }
+    };
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+        From $($skip:ident)*
+    ) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            ($display $debug no $into $deref $deref_mut
+                $partial_eq $eq $hash $clone)
+            $($skip)*
+        }
+    };
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+        Into $($skip:ident)*
+    ) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            ($display $debug $from no $deref $deref_mut
+                $partial_eq $eq $hash $clone)
+            $($skip)*
+        }
+    };
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+        Deref $($skip:ident)*
+    ) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            ($display $debug $from $into no $deref_mut
+                $partial_eq $eq $hash $clone)
+            $($skip)*
+        }
+    };
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+        DerefMut $($skip:ident)*
+    ) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            ($display $debug $from $into $deref no
+                $partial_eq $eq $hash $clone)
+            $($skip)*
+        }
+    };
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+        PartialEq $($skip:ident)*
+    ) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            ($display $debug $from $into $deref $deref_mut
+                no $eq $hash $clone)
+            $($skip)*
+        }
+    };
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+        Eq $($skip:ident)*
+    ) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            ($display $debug $from $into $deref $deref_mut
+                $partial_eq no $hash $clone)
+            $($skip)*
+        }
+    };
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+        Hash $($skip:ident)*
+    ) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            ($display $debug $from $into $deref $deref_mut
+                $partial_eq $eq no $clone)
+            $($skip)*
+        }
+    };
+    (@skip($name:ident, $inner:ty)
+        ($display:tt $debug:tt $from:tt $into:tt $deref:tt $deref_mut:tt
+            $partial_eq:tt $eq:tt $hash:tt $clone:tt)
+        Clone $($skip:ident)*
+    ) => {
+        $crate::impl_newtype! {
+            @skip($name, $inner)
+            ($display $debug $from $into $deref $deref_mut
+                $partial_eq $eq $hash no)
+            $($skip)*
+        }
+    };
+    (@skip($name:ident, $inner:ty) $any:tt $unk:ident $($skip:ident)*) => {
+        compile_error! { concat!(
+            "cannot skip `", stringify!($unk), "` in `impl_newtype!`, expected one of ",
+            "Display, Debug, From, Into, Deref, DerefMut, PartialEq, Eq, Hash, Clone"
+        ) }
+    };
+
+    (@emit($name:ident, $inner:ty) no $trait:ident) => {};
+    (@emit($name:ident, $inner:ty) yes Display) => {
+        $crate::implem! {
+            for $name {
+                Display {
+                    |&self, fmt| std::fmt::Display::fmt(&self.0, fmt)
+                }
+            }
+        }
+    };
+    (@emit($name:ident, $inner:ty) yes Debug) => {
+        $crate::implem! {
+            for $name {
+                Debug {
+                    |&self, fmt| fmt.debug_tuple(stringify!($name)).field(&self.0).finish()
+                }
+            }
+        }
+    };
+    (@emit($name:ident, $inner:ty) yes From) => {
+        $crate::implem! {
+            for $name {
+                From<$inner> {
+                    |inner| Self(inner)
+                }
+            }
+        }
+    };
+    (@emit($name:ident, $inner:ty) yes Into) => {
+        $crate::implem! {
+            for $name {
+                Into<$inner> {
+                    |self| self.0
+                }
+            }
+        }
+    };
+    (@emit($name:ident, $inner:ty) yes Deref) => {
+        $crate::implem! {
+            for $name {
+                Deref<Target = $inner> {
+                    |&self| &self.0
+                }
+            }
+        }
+    };
+    (@emit($name:ident, $inner:ty) yes DerefMut) => {
+        $crate::implem! {
+            for $name {
+                DerefMut {
+                    |&mut self| &mut self.0
+                }
+            }
+        }
+    };
+    (@emit($name:ident, $inner:ty) yes PartialEq) => {
+        $crate::implem! {
+            for $name {
+                PartialEq {
+                    |&self, other| self.0 == other.0
+                }
+            }
+        }
+    };
+    (@emit($name:ident, $inner:ty) yes Eq) => {
+        $crate::implem! {
+            for $name {
+                impl std::cmp::Eq {}
+            }
+        }
+    };
+    (@emit($name:ident, $inner:ty) yes Hash) => {
+        $crate::implem! {
+            for $name {
+                impl std::hash::Hash {
+                    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                        std::hash::Hash::hash(&self.0, state)
+                    }
+                }
+            }
+        }
+    };
+    (@emit($name:ident, $inner:ty) yes Clone) => {
+        $crate::implem! {
+            for $name {
+                Clone {
+                    |&self| Self(std::clone::Clone::clone(&self.0))
+                }
+            }
+        }
+    };
+}
+
+/// Implements `Display` and `Error` for an error enum, one message per variant.
+///
+/// Each variant is written as a match pattern without the `Self::` prefix, followed by `=>` and
+/// the `format!`-style arguments of its message. The enum must implement `Debug` itself, usually
+/// with a derive.
+///
+/// ```rust
+/// # use implem::impl_error_type;
+/// #[derive(Debug)]
+/// pub enum ConfigError {
+///     Missing,
+///     Io(std::io::Error),
+///     Invalid { key: String, line: usize },
+/// }
+/// impl_error_type!(ConfigError;
+///     Missing => "no configuration file",
+///     Io(e) => "could not read configuration: {}", e,
+///     Invalid { key, line } => "invalid key `{}` on line {}", key, line,
+/// );
+///
+/// assert_eq!(ConfigError::Missing.to_string(), "no configuration file");
+/// let io = std::io::Error::new(std::io::ErrorKind::Other, "disk on fire");
+/// assert_eq!(ConfigError::Io(io).to_string(), "could not read configuration: disk on fire");
+/// let invalid = ConfigError::Invalid { key: "colour".into(), line: 3 };
+/// assert_eq!(invalid.to_string(), "invalid key `colour` on line 3");
+///
+/// fn load() -> Result<(), Box<dyn std::error::Error>> {
+///     Err(ConfigError::Missing)?
+/// }
+/// assert_eq!(load().unwrap_err().to_string(), "no configuration file");
+/// ```
+#[macro_export]
+macro_rules! impl_error_type {
+    ($name:ident; $variant:ident => $msg:literal $($rest:tt)*) => {
+        $crate::impl_error_type! { @munch($name) [] $variant [] => ($msg) $($rest)* }
+    };
+    ($name:ident; $variant:ident $fields:tt => $msg:literal $($rest:tt)*) => {
+        $crate::impl_error_type! { @munch($name) [] $variant [$fields] => ($msg) $($rest)* }
+    };
+
+    // One step per variant: the message arguments of the current variant go until the next
+    // `, Variant =>` or `, Variant(..) =>`. Variants with up to two arguments are handled in a
+    // single step, each further argument takes one more step.
+    (@munch($name:ident) [$($done:tt)*] $variant:ident [$($fields:tt)*] => ($($msg:tt)*) $(,)?) => {
+        $crate::impl_error_type! { @emit($name) $($done)* ($variant [$($fields)*] => $($msg)*) }
+    };
+    (@munch($name:ident) [$($done:tt)*] $variant:ident [$($fields:tt)*] => ($($msg:tt)*)
+        , $next:ident => $next_msg:literal $($rest:tt)*
+    ) => {
+        $crate::impl_error_type! {
+            @munch($name) [$($done)* ($variant [$($fields)*] => $($msg)*)]
+            $next [] => ($next_msg) $($rest)*
+        }
+    };
+    (@munch($name:ident) [$($done:tt)*] $variant:ident [$($fields:tt)*] => ($($msg:tt)*)
+        , $next:ident $next_fields:tt => $next_msg:literal $($rest:tt)*
+    ) => {
+        $crate::impl_error_type! {
+            @munch($name) [$($done)* ($variant [$($fields)*] => $($msg)*)]
+            $next [$next_fields] => ($next_msg) $($rest)*
+        }
+    };
+    (@munch($name:ident) [$($done:tt)*] $variant:ident [$($fields:tt)*] => ($($msg:tt)*)
+        , $arg_1:expr, $next:ident => $next_msg:literal $($rest:tt)*
+    ) => {
+        $crate::impl_error_type! {
+            @munch($name) [$($done)* ($variant [$($fields)*] => $($msg)*, $arg_1)]
+            $next [] => ($next_msg) $($rest)*
+        }
+    };
+    (@munch($name:ident) [$($done:tt)*] $variant:ident [$($fields:tt)*] => ($($msg:tt)*)
+        , $arg_1:expr, $next:ident $next_fields:tt => $next_msg:literal $($rest:tt)*
+    ) => {
+        $crate::impl_error_type! {
+            @munch($name) [$($done)* ($variant [$($fields)*] => $($msg)*, $arg_1)]
+            $next [$next_fields] => ($next_msg) $($rest)*
+        }
+    };
+    (@munch($name:ident) [$($done:tt)*] $variant:ident [$($fields:tt)*] => ($($msg:tt)*)
+        , $arg_1:expr, $arg_2:expr, $next:ident => $next_msg:literal $($rest:tt)*
+    ) => {
+        $crate::impl_error_type! {
+            @munch($name) [$($done)* ($variant [$($fields)*] => $($msg)*, $arg_1, $arg_2)]
+            $next [] => ($next_msg) $($rest)*
+        }
+    };
+    (@munch($name:ident) [$($done:tt)*] $variant:ident [$($fields:tt)*] => ($($msg:tt)*)
+        , $arg_1:expr, $arg_2:expr, $next:ident $next_fields:tt => $next_msg:literal $($rest:tt)*
+    ) => {
+        $crate::impl_error_type! {
+            @munch($name) [$($done)* ($variant [$($fields)*] => $($msg)*, $arg_1, $arg_2)]
+            $next [$next_fields] => ($next_msg) $($rest)*
+        }
+    };
+    (@munch($name:ident) [$($done:tt)*] $variant:ident [$($fields:tt)*] => ($($msg:tt)*)
+        , $arg:expr $(, $($rest:tt)*)?
+    ) => {
+        $crate::impl_error_type! {
+            @munch($name) [$($done)*] $variant [$($fields)*] => ($($msg)*, $arg) $(, $($rest)*)?
+        }
+    };
+
+    (@emit($name:ident) $((
+        $variant:ident [$($fields:tt)*] => $msg:literal $(, $arg:expr)*
+    ))*) => {
+        $crate::implem! {
+            for $name {
+                Display {
+                    |&self, fmt| match self {
+                        $(
+                            Self::$variant $($fields)* => write!(fmt, $msg $(, $arg)*),
+                        )*
+                    }
+                }
+                Error {}
+            }
+        }
+    };
+}
+
+/// Generates a `#[test]` checking that values survive a `Display` then `FromStr` roundtrip.
+///
+/// `test_roundtrip!(MyType, value1, value2)` generates a `roundtrip` test function asserting that
+/// each `value.to_string().parse::<MyType>()` gives back `value`, which requires `MyType` to
+/// implement `PartialEq` and `Debug`. Name the function with `my_name => MyType, ...` to have more
+/// than one in a module; attributes such as `#[should_panic]` go first.
+///
+/// ```rust
+/// # use implem::{implem, test_roundtrip};
+/// #[derive(Debug, PartialEq)]
+/// pub struct Port(u16);
+/// implem! {
+///     for Port {
+///         DisplayFromStr<Err = std::num::ParseIntError> {
+///             |&self, fmt| write!(fmt, ":{}", self.0),
+///             |s| s.trim_start_matches(':').parse().map(Self),
+///         }
+///     }
+/// }
+///
+/// test_roundtrip!(Port, Port(0), Port(80), Port(u16::MAX));
+/// test_roundtrip!(port_strings_roundtrip => Port, ":8080".parse().unwrap());
+/// ```
+#[macro_export]
+macro_rules! test_roundtrip {
+    ($(#[$attr:meta])* $name:ident => $ty:ty, $($value:expr),+ $(,)?) => {
+        $(#[$attr])*
+        #[test]
+        fn $name() {
+            $({
+                let value: $ty = $value;
+                let string = std::string::ToString::to_string(&value);
+                let parsed = <$ty as std::str::FromStr>::from_str(&string);
+                assert_eq!(
+                    parsed.as_ref().ok(),
+                    Some(&value),
+                    "`{}` does not parse back as `{:?}`",
+                    string,
+                    value,
+                );
+            })+
+        }
+    };
+    ($(#[$attr:meta])* $ty:ty, $($value:expr),+ $(,)?) => {
+        $crate::test_roundtrip! { $(#[$attr])* roundtrip => $ty, $($value),+ }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal {
+    // Checks the headers of the blocks of an `implem!` invocation, once before expanding them, in
+    // particular that the angle brackets of the self types are balanced: `implem!` parses them as
+    // `ty` fragments, and failing to do so is a hard error with an unhelpful message. Expands to
+    // nothing if the headers look fine, malformed blocks are left to `implem!`'s own error.
+    //
+    // The header of a block is everything up to its `{ ... }` body. It is split off by arms
+    // matching bodies at fixed positions, eight tokens at a time, so that the recursion only grows
+    // by about one level per block; each header is then checked separately.
+    { @check_blocks ($($header:tt)*) { $($body:tt)* } $($tail:tt)* } => {
+        $crate::internal! { @check_header (body) $($header)* }
+        $crate::internal! { @check_blocks () $($tail)* }
+    };
+    { @check_blocks ($($header:tt)*) $t1:tt { $($body:tt)* } $($tail:tt)* } => {
+        $crate::internal! { @check_header (body) $($header)* $t1 }
+        $crate::internal! { @check_blocks () $($tail)* }
+    };
+    { @check_blocks ($($header:tt)*) $t1:tt $t2:tt { $($body:tt)* } $($tail:tt)* } => {
+        $crate::internal! { @check_header (body) $($header)* $t1 $t2 }
+        $crate::internal! { @check_blocks () $($tail)* }
+    };
+    { @check_blocks ($($header:tt)*) $t1:tt $t2:tt $t3:tt { $($body:tt)* } $($tail:tt)* } => {
+        $crate::internal! { @check_header (body) $($header)* $t1 $t2 $t3 }
+        $crate::internal! { @check_blocks () $($tail)* }
+    };
+    { @check_blocks ($($header:tt)*)
+        $t1:tt $t2:tt $t3:tt $t4:tt { $($body:tt)* } $($tail:tt)*
+    } => {
+        $crate::internal! { @check_header (body) $($header)* $t1 $t2 $t3 $t4 }
+        $crate::internal! { @check_blocks () $($tail)* }
+    };
+    { @check_blocks ($($header:tt)*)
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt { $($body:tt)* } $($tail:tt)*
+    } => {
+        $crate::internal! { @check_header (body) $($header)* $t1 $t2 $t3 $t4 $t5 }
+        $crate::internal! { @check_blocks () $($tail)* }
+    };
+    { @check_blocks ($($header:tt)*)
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt { $($body:tt)* } $($tail:tt)*
+    } => {
+        $crate::internal! { @check_header (body) $($header)* $t1 $t2 $t3 $t4 $t5 $t6 }
+        $crate::internal! { @check_blocks () $($tail)* }
+    };
+    { @check_blocks ($($header:tt)*)
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt { $($body:tt)* } $($tail:tt)*
+    } => {
+        $crate::internal! { @check_header (body) $($header)* $t1 $t2 $t3 $t4 $t5 $t6 $t7 }
+        $crate::internal! { @check_blocks () $($tail)* }
+    };
+    { @check_blocks ($($header:tt)*)
+        $t1:tt $t2:tt $t3:tt $t4:tt $t5:tt $t6:tt $t7:tt $t8:tt $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @check_blocks ($($header)* $t1 $t2 $t3 $t4 $t5 $t6 $t7 $t8) $($tail)*
+        }
+    };
+    { @check_blocks () } => {};
+    // No body left, the header is the rest of the input.
+    { @check_blocks ($($header:tt)*) $($tail:tt)* } => {
+        $crate::internal! { @check_header () $($header)* $($tail)* }
+    };
+    { @check_header ($($body:ident)?) impl ($($t_params:tt)*) for $($tail:tt)* } => {
+        $crate::internal! { @check_self_ty ($($body)?) [] () $($tail)* }
+    };
+    { @check_header ($($body:ident)?) for $($tail:tt)* } => {
+        $crate::internal! { @check_self_ty ($($body)?) [] () $($tail)* }
+    };
+    { @check_header $($tail:tt)* } => {};
+    { @check_self_ty ($($body:ident)?) [] ( $($self_ty:tt)+ ) where $($tail:tt)* } => {};
+    { @check_self_ty (body) [] ( $($self_ty:tt)* ) } => {};
+    { @check_self_ty ($($body:ident)?) [$($depth:tt)+] ( $($self_ty:tt)* ) where $($tail:tt)* } => {
+        $crate::internal! { @self_ty_unclosed $($self_ty)* }
+    };
+    { @check_self_ty (body) [$($depth:tt)+] ( $($self_ty:tt)* ) } => {
+        $crate::internal! { @self_ty_unclosed $($self_ty)* }
+    };
+    { @check_self_ty ($($body:ident)?) [$($depth:tt)*] ( $($self_ty:tt)* ) < $($tail:tt)* } => {
+        $crate::internal! { @check_self_ty ($($body)?) [< $($depth)*] ($($self_ty)* <) $($tail)* }
+    };
+    { @check_self_ty ($($body:ident)?) [$($depth:tt)*] ( $($self_ty:tt)* ) << $($tail:tt)* } => {
+        $crate::internal! {
+            @check_self_ty ($($body)?) [< < $($depth)*] ($($self_ty)* <<) $($tail)*
+        }
+    };
+    { @check_self_ty ($($body:ident)?) [< $($depth:tt)*] ( $($self_ty:tt)* ) > $($tail:tt)* } => {
+        $crate::internal! { @check_self_ty ($($body)?) [$($depth)*] ($($self_ty)* >) $($tail)* }
+    };
+    {
+        @check_self_ty ($($body:ident)?) [< < $($depth:tt)*] ( $($self_ty:tt)* ) >> $($tail:tt)*
+    } => {
+        $crate::internal! { @check_self_ty ($($body)?) [$($depth)*] ($($self_ty)* >>) $($tail)* }
+    };
+    { @check_self_ty ($($body:ident)?) [$($depth:tt)*] ( $($self_ty:tt)* ) > $($tail:tt)* } => {
+        $crate::internal! { @self_ty_unexpected (>) $($self_ty)* }
+    };
+    { @check_self_ty ($($body:ident)?) [$($depth:tt)*] ( $($self_ty:tt)* ) >> $($tail:tt)* } => {
+        $crate::internal! { @self_ty_unexpected (>>) $($self_ty)* }
+    };
+    {
+        @check_self_ty ($($body:ident)?) [$($depth:tt)*] ( $($self_ty:tt)* ) $token:tt $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @check_self_ty ($($body)?) [$($depth)*] ($($self_ty)* $token) $($tail)*
+        }
+    };
+    { @check_self_ty () [$($depth:tt)*] ( $($self_ty:tt)* ) } => {
+        compile_error! { concat!(
+            "expected `{ ... }` definitions after the type `", stringify!($($self_ty)*), "`"
+        ) }
+    };
+    { @self_ty_unclosed $($self_ty:tt)* } => {
+        compile_error! { concat!(
+            "failed to parse the type after `for`, unclosed `<` in `", stringify!($($self_ty)*),
+            "`; check your angle brackets and lifetimes"
+        ) }
+    };
+    { @self_ty_unexpected ($token:tt) $($self_ty:tt)* } => {
+        compile_error! { concat!(
+            "failed to parse the type after `for`, unexpected `", stringify!($token), "` after `",
+            stringify!($($self_ty)*), "`; check your angle brackets and lifetimes"
+        ) }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Display {
+            |&$slf:ident, $fmt:pat| $def:expr
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Display for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Debug {
+            |&$slf:ident, $fmt:pat| $def:expr
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Debug for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Debug {
+            struct $name:literal { $($fields:tt)* }
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Debug for $self_ty
+        where $($where_clauses)* {
+            $crate::internal! {
+                @find_self { @debug_struct_fmt $name { $($fields)* } } $($fields)*
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Debug {
+            list [ $($entries:tt)* ]
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Debug for $self_ty
+        where $($where_clauses)* {
+            $crate::internal! {
+                @find_self { @debug_list_fmt [ $($entries)* ] } $($entries)*
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Debug {
+            tuple $name:literal ( $($fields:tt)* )
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Debug for $self_ty
+        where $($where_clauses)* {
+            $crate::internal! {
+                @find_self { @debug_tuple_fmt $name ( $($fields)* ) } $($fields)*
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @debug_struct_fmt
+        $name:literal { $($label:literal => $val:expr),* $(,)? } ($slf:ident)
+    } => {
+        fn fmt(&$slf, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fmt.debug_struct($name)
+                $(.field($label, $val))*
+                .finish()
+        }
+    };
+    { @debug_struct_fmt $name:literal { $($fields:tt)* } ($slf:ident) } => {
+        compile_error! { concat!(
+            "unexpected fields in `Debug { struct ", stringify!($name), " { ... } }`, ",
+            "expected `\"field\" => expr` separated by commas",
+        ) }
+    };
+    { @debug_tuple_fmt $name:literal ( $($val:expr),* $(,)? ) ($slf:ident) } => {
+        fn fmt(&$slf, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fmt.debug_tuple($name)
+                $(.field($val))*
+                .finish()
+        }
+    };
+    { @debug_list_fmt [ $entries:expr ] ($slf:ident) } => {
+        fn fmt(&$slf, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+            fmt.debug_list().entries($entries).finish()
+        }
+    };
+    // `self` is hygienic: look for the user's `self` token in `$tokens` and pass it to `$cb`, fall
+    // back on our own `self` when the user never mentions it.
+    { @find_self { $($cb:tt)* } } => {
+        $crate::internal! { $($cb)* (self) }
+    };
+    { @find_self { $($cb:tt)* } $id:ident $($tokens:tt)* } => {
+        $crate::internal! { @find_self_ident { $($cb)* } ($id) $id $($tokens)* }
+    };
+    { @find_self { $($cb:tt)* } ( $($inner:tt)* ) $($tokens:tt)* } => {
+        $crate::internal! { @find_self { $($cb)* } $($inner)* $($tokens)* }
+    };
+    { @find_self { $($cb:tt)* } [ $($inner:tt)* ] $($tokens:tt)* } => {
+        $crate::internal! { @find_self { $($cb)* } $($inner)* $($tokens)* }
+    };
+    { @find_self { $($cb:tt)* } { $($inner:tt)* } $($tokens:tt)* } => {
+        $crate::internal! { @find_self { $($cb)* } $($inner)* $($tokens)* }
+    };
+    { @find_self { $($cb:tt)* } $other:tt $($tokens:tt)* } => {
+        $crate::internal! { @find_self { $($cb)* } $($tokens)* }
+    };
+    { @find_self_ident { $($cb:tt)* } ($slf:ident) self $($tokens:tt)* } => {
+        $crate::internal! { $($cb)* ($slf) }
+    };
+    { @find_self_ident { $($cb:tt)* } ($id:ident) $other:ident $($tokens:tt)* } => {
+        $crate::internal! { @find_self { $($cb)* } $($tokens)* }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Binary {
+            |&$slf:ident, $fmt:pat| $def:expr
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Binary for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Octal {
+            |&$slf:ident, $fmt:pat| $def:expr
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Octal for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        LowerHex {
+            |&$slf:ident, $fmt:pat| $def:expr
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::LowerHex for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        UpperHex {
+            |&$slf:ident, $fmt:pat| $def:expr
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::UpperHex for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Pointer {
+            |&$slf:ident, $fmt:pat| $def:expr
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Pointer for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        LowerExp {
+            |&$slf:ident, $fmt:pat| $def:expr
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::LowerExp for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        UpperExp {
+            |&$slf:ident, $fmt:pat| $def:expr
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::UpperExp for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DisplayAlt {
+            short: |&$slf:ident, $fmt:pat| $def:expr,
+            alt: |&$slf_alt:ident, $fmt_alt:pat| $def_alt:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Display for $self_ty
+        where $($where_clause)* {
+            fn fmt(&$slf, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                if fmt.alternate() {
+                    let $fmt_alt = fmt;
+                    $def_alt
+                } else {
+                    let $fmt = fmt;
+                    $def
+                }
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DisplayViaDebug {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Display for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                std::fmt::Debug::fmt(self, fmt)
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DebugViaDisplay {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Debug for $self_ty
+        where $($where_clauses)* {
+            fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+                std::fmt::Display::fmt(self, fmt)
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FmtWrite {
+            |&mut $slf:ident, $s:pat| $def:expr
+            $(
+                , |&mut $slf_char:ident, $c:pat| $def_char:expr
+            )?
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::fmt::Write for $self_ty
+        where $($where_clauses)* {
+            fn write_str(&mut $slf, $s: &str) -> std::fmt::Result {
+                $def
+            }
+            $(
+                fn write_char(&mut $slf_char, $c: char) -> std::fmt::Result {
+                    $def_char
+                }
+            )?
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FromStr<Err = $err_ty:ty> {
+            |$s:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::str::FromStr for $self_ty
+        where $($where_clauses)* {
+            type Err = $err_ty;
+            fn from_str($s: &str) -> std::result::Result<Self, $err_ty> {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DisplayFromStr {
+            $($stuff:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            DisplayFromStr<Err = String> { $($stuff)* }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DisplayFromStr<Err = $err_ty:ty> {
+            |&$slf:ident, $fmt:pat| $def:expr,
+            |$s:pat| $def_from_str:expr
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            Display { |&$slf, $fmt| $def }
+            $(#[$attr])*
+            FromStr<Err = $err_ty> { |$s| $def_from_str }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        TryFrom<$src_ty:ty> {
+            type Error = $err_ty:ty;
+            |$arg:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::convert::TryFrom<$src_ty> for $self_ty
+        where $($where_clause)* {
+            type Error = $err_ty;
+            fn try_from($arg: $src_ty) -> std::result::Result<Self, $err_ty> {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FromStrViaTryFrom {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::str::FromStr for $self_ty
+        where $($where_clause)* {
+            type Err = <Self as std::convert::TryFrom<&'static str>>::Error;
+            fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+                <Self as std::convert::TryFrom<&str>>::try_from(s)
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FromStrViaTryFrom<Err = $err_ty:ty> {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::str::FromStr for $self_ty
+        where $($where_clause)* {
+            type Err = $err_ty;
+            fn from_str(s: &str) -> std::result::Result<Self, $err_ty> {
+                <Self as std::convert::TryFrom<&str>>::try_from(s).map_err(std::convert::Into::into)
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        From<$src_ty:ty> {
+            |$src:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::convert::From<$src_ty> for $self_ty
+        where $($where_clauses)* {
+            fn from($src: $src_ty) -> Self {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Into<$tgt_ty:ty> {
+            |$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::convert::Into<$tgt_ty> for $self_ty
+        where $($where_clauses)* {
+            $crate::internal! { @into_fn ($slf) $slf ($tgt_ty) $def }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    // `self` is hygienic, keep the user's token when the receiver is called `self`.
+    { @into_fn ($slf:ident) self ($tgt_ty:ty) $def:expr } => {
+        fn into($slf) -> $tgt_ty {
+            $def
+        }
+    };
+    { @into_fn ($slf:ident) $other:ident ($tgt_ty:ty) $def:expr } => {
+        fn into(self) -> $tgt_ty {
+            let $slf = self;
+            $def
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Clone {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::clone::Clone for $self_ty
+        where $($where_clauses)* {
+            fn clone(&$slf) -> Self {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Add $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @binop(Add, add)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Sub $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @binop(Sub, sub)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Mul $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @binop(Mul, mul)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Div $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @binop(Div, div)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Rem $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @binop(Rem, rem)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        BitAnd $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @binop(BitAnd, bitand)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        BitOr $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @binop(BitOr, bitor)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        BitXor $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @binop(BitXor, bitxor)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Shl $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @binop(Shl, shl)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Shr $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @binop(Shr, shr)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            $($stuff)*
+        }
+    };
+    // Binary operators, `Rhs` defaults to `Self` like in `std::ops`.
+    { @binop($($op:tt)*)
+        @( $($t_params:tt)* )( $($where_clauses:tt)* )($self_ty:ty)
+        $(#[$attr:meta])*
+        <Output = $out_ty:ty> { $($def:tt)* }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @binop($($op)*)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            <Self, Output = $out_ty> { $($def)* }
+            $($tail)*
+        }
+    };
+    { @binop($($op:tt)*)
+        @( $($t_params:tt)* )( $($where_clauses:tt)* )($self_ty:ty)
+        $(#[$attr:meta])*
+        <$rhs_ty:ty, Output = $out_ty:ty> {
+            |$slf:ident, $rhs:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @binop($($op)*)
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $(#[$attr])*
+            <$rhs_ty, Output = $out_ty> { |$slf, $rhs: $rhs_ty| $def }
+            $($tail)*
+        }
+    };
+    { @binop($op_trait:ident, $op_fn:ident)
+        @( $($t_params:tt)* )( $($where_clauses:tt)* )($self_ty:ty)
+        $(#[$attr:meta])*
+        <$rhs_ty:ty, Output = $out_ty:ty> {
+            |$slf:ident, $rhs:ident: $rhs_annot:ty| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::$op_trait<$rhs_ty> for $self_ty
+        where $($where_clauses)* {
+            type Output = $out_ty;
+            $crate::internal! { @binop_fn $op_fn ($slf) $slf ($rhs: $rhs_annot) -> $out_ty { $def } }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @binop($op_trait:ident, $op_fn:ident) $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unexpected `", stringify!($op_trait), "` definition, expected\n",
+            "- `<Rhs, Output = Out> { |self, rhs| ... }`, or\n",
+            "- `<Output = Out> { |self, rhs| ... }` when `Rhs` is `Self`",
+        ) }
+    };
+    // `self` is hygienic, keep the user's token when the receiver is called `self`.
+    { @binop_fn $op_fn:ident ($slf:ident) self ($rhs:ident: $rhs_ty:ty) -> $out_ty:ty { $def:expr } } => {
+        fn $op_fn($slf, $rhs: $rhs_ty) -> $out_ty {
+            $def
+        }
+    };
+    { @binop_fn $op_fn:ident ($slf:ident) $other:ident ($rhs:ident: $rhs_ty:ty) -> $out_ty:ty { $def:expr } } => {
+        fn $op_fn(self, $rhs: $rhs_ty) -> $out_ty {
+            let $slf = self;
+            $def
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Default {
+            || $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::default::Default for $self_ty
+        where $($where_clauses)* {
+            fn default() -> Self {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ToOwned<Owned = $owned_ty:ty> {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::borrow::ToOwned for $self_ty
+        where $($where_clauses)* {
+            type Owned = $owned_ty;
+            fn to_owned(&$slf) -> $owned_ty {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Deref<Target = $tgt_ty:ty> {
+            |&$slf:ident| $def:expr,
+            |&mut $slf_mut:ident| $def_mut:expr
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Deref<Target = $tgt_ty> { |&$slf| $def }
+            $(#[$attr])*
+            DerefMut { |&mut $slf_mut| $def_mut }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Deref<Target = $tgt_ty:ty> {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::Deref for $self_ty
+        where $($where_clause)* {
+            type Target = $tgt_ty;
+            fn deref(&$slf) -> &$tgt_ty {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DerefMut {
+            |&mut $slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::DerefMut for $self_ty
+        where $($where_clause)* {
+            fn deref_mut(&mut $slf) -> &mut <Self as std::ops::Deref>::Target {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DerefStr {}
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Deref<Target = str> { |&self| &self.0 }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DerefStr {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Deref<Target = str> { |&$slf| $def }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DerefSlice<$elm_ty:ty> {}
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Deref<Target = [$elm_ty]> { |&self| &self.0 }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DerefSlice<$elm_ty:ty> {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Deref<Target = [$elm_ty]> { |&$slf| $def }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Deref1 { $inner_ty:ty }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Deref<Target = $inner_ty> { |&self| &self.0, |&mut self| &mut self.0 }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Index<$idx_ty:ty, Output = $out_ty:ty> {
+            |&$slf:ident, $idx:pat| $def:expr,
+            |&mut $slf_mut:ident, $idx_mut:pat| $def_mut:expr
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Index<$idx_ty, Output = $out_ty> { |&$slf, $idx| $def }
+            $(#[$attr])*
+            IndexMut<$idx_ty> { |&mut $slf_mut, $idx_mut| $def_mut }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Index<$idx_ty:ty, Output = $out_ty:ty> {
+            |&$slf:ident, $idx:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::Index<$idx_ty> for $self_ty
+        where $($where_clause)* {
+            type Output = $out_ty;
+            fn index(&$slf, $idx: $idx_ty) -> &Self::Output {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        IndexMut<$idx_ty:ty> {
+            |&mut $slf:ident, $idx:pat| $def_mut:expr
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::IndexMut<$idx_ty> for $self_ty
+        where $($where_clause)* {
+            fn index_mut(&mut $slf, $idx: $idx_ty) -> &mut Self::Output {
+                $def_mut
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        RangeBounds<$elm_ty:ty> {
+            |&$slf_start:ident| $def_start:expr,
+            |&$slf_end:ident| $def_end:expr
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::RangeBounds<$elm_ty> for $self_ty
+        where $($where_clause)* {
+            fn start_bound(&$slf_start) -> std::ops::Bound<&$elm_ty> {
+                $def_start
+            }
+            fn end_bound(&$slf_end) -> std::ops::Bound<&$elm_ty> {
+                $def_end
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Unpin {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::marker::Unpin for $self_ty
+        where $($where_clause)* {}
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Error {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::error::Error for $self_ty
+        where $($where_clause)* {}
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Error {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::error::Error for $self_ty
+        where $($where_clause)* {
+            fn source(&$slf) -> Option<&(dyn std::error::Error + 'static)> {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        unsafe Send {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        unsafe impl<$($t_params)*> std::marker::Send for $self_ty
+        where $($where_clause)* {}
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Send $($stuff:tt)*
+    } => {
+        compile_error! {
+            "implementing `Send` is unsafe, write `unsafe Send {}` to make the assertion explicit"
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        unsafe Sync {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        unsafe impl<$($t_params)*> std::marker::Sync for $self_ty
+        where $($where_clause)* {}
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Sync $($stuff:tt)*
+    } => {
+        compile_error! {
+            "implementing `Sync` is unsafe, write `unsafe Sync {}` to make the assertion explicit"
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        UnwindSafe {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::panic::UnwindSafe for $self_ty
+        where $($where_clause)* {}
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        RefUnwindSafe {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::panic::RefUnwindSafe for $self_ty
+        where $($where_clause)* {}
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Iterator<Item = $item_ty:ty> {
+            $($methods:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::iter::Iterator for $self_ty
+        where $($where_clause)* {
+            type Item = $item_ty;
+            $crate::internal! { @iterator_methods $($methods)* }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @iterator_methods next: |&mut $slf:ident| $def:expr $(, $($rest:tt)*)? } => {
+        fn next(&mut $slf) -> Option<Self::Item> {
+            $def
+        }
+        $crate::internal! { @iterator_methods $($($rest)*)? }
+    };
+    { @iterator_methods size_hint: |&$slf:ident| $def:expr $(, $($rest:tt)*)? } => {
+        fn size_hint(&$slf) -> (usize, Option<usize>) {
+            $def
+        }
+        $crate::internal! { @iterator_methods $($($rest)*)? }
+    };
+    { @iterator_methods nth: |&mut $slf:ident, $n:pat| $def:expr $(, $($rest:tt)*)? } => {
+        fn nth(&mut $slf, $n: usize) -> Option<Self::Item> {
+            $def
+        }
+        $crate::internal! { @iterator_methods $($($rest)*)? }
+    };
+    { @iterator_methods
+        fold: |$slf:ident, $init:pat, $f:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn fold<B, F>($slf, $init: B, $f: F) -> B
+        where F: FnMut(B, Self::Item) -> B {
+            $def
+        }
+        $crate::internal! { @iterator_methods $($($rest)*)? }
+    };
+    { @iterator_methods } => {};
+    { @iterator_methods $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `Iterator` method `", stringify!($label), "`, expected one of\n",
+            "- next: |&mut self| ...\n",
+            "- size_hint: |&self| ...\n",
+            "- nth: |&mut self, n| ...\n",
+            "- fold: |self, init, f| ...",
+        ) }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FusedIterator {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::iter::FusedIterator for $self_ty
+        where $($where_clause)* {}
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Hasher {
+            |&mut $slf:ident, $bytes:pat| $def:expr,
+            |&$slf_finish:ident| $def_finish:expr
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::hash::Hasher for $self_ty
+        where $($where_clause)* {
+            fn write(&mut $slf, $bytes: &[u8]) {
+                $def
+            }
+            fn finish(&$slf_finish) -> u64 {
+                $def_finish
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        BuildHasher<Hasher = $hasher_ty:ty> {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::hash::BuildHasher for $self_ty
+        where $($where_clause)* {
+            type Hasher = $hasher_ty;
+            fn build_hasher(&$slf) -> $hasher_ty {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Read {
+            |&mut $slf:ident, $buf:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::io::Read for $self_ty
+        where $($where_clause)* {
+            fn read(&mut $slf, $buf: &mut [u8]) -> std::io::Result<usize> {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Write {
+            |&mut $slf:ident, $buf:pat| $def:expr,
+            |&mut $slf_flush:ident| $def_flush:expr
+            $(
+                , |&mut $slf_all:ident, $buf_all:pat| $def_all:expr
+            )?
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::io::Write for $self_ty
+        where $($where_clause)* {
+            fn write(&mut $slf, $buf: &[u8]) -> std::io::Result<usize> {
+                $def
+            }
+            fn flush(&mut $slf_flush) -> std::io::Result<()> {
+                $def_flush
+            }
+            $(
+                fn write_all(&mut $slf_all, $buf_all: &[u8]) -> std::io::Result<()> {
+                    $def_all
+                }
+            )?
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Seek {
+            |&mut $slf:ident, $pos:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::io::Seek for $self_ty
+        where $($where_clause)* {
+            fn seek(&mut $slf, $pos: std::io::SeekFrom) -> std::io::Result<u64> {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        IoSeek $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Seek $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        BufRead {
+            |&mut $slf:ident| $def:expr,
+            |&mut $slf_consume:ident, $amt:pat| $def_consume:expr
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::io::BufRead for $self_ty
+        where $($where_clause)* {
+            fn fill_buf(&mut $slf) -> std::io::Result<&[u8]> {
+                $def
+            }
+            fn consume(&mut $slf_consume, $amt: usize) {
+                $def_consume
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        IoBufRead $($stuff:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            BufRead $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ToSocketAddrs<Iter = $iter_ty:ty> {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::net::ToSocketAddrs for $self_ty
+        where $($where_clause)* {
+            type Iter = $iter_ty;
+            fn to_socket_addrs(&$slf) -> std::io::Result<$iter_ty> {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        PartialEq {
+            |&$slf:ident, $other:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            PartialEq<Self> { |&$slf, $other| $def }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        PartialEq<$rhs_ty:ty> {
+            |&$slf:ident, $other:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::cmp::PartialEq<$rhs_ty> for $self_ty
+        where $($where_clause)* {
+            fn eq(&$slf, $other: &$rhs_ty) -> bool {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Comparable<Key = $key_ty:ty> {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        impl<$($t_params)*> $self_ty
+        where $($where_clause)* {
+            #[doc(hidden)]
+            fn __implem_comparable_key(&$slf) -> $key_ty {
+                $def
+            }
+        }
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::cmp::PartialEq for $self_ty
+        where $($where_clause)* {
+            fn eq(&self, other: &Self) -> bool {
+                self.__implem_comparable_key() == other.__implem_comparable_key()
+            }
+        }
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::cmp::Eq for $self_ty
+        where $($where_clause)* {}
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::cmp::PartialOrd for $self_ty
+        where $($where_clause)* {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(std::cmp::Ord::cmp(self, other))
+            }
+        }
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::cmp::Ord for $self_ty
+        where $($where_clause)* {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                std::cmp::Ord::cmp(
+                    &self.__implem_comparable_key(),
+                    &other.__implem_comparable_key(),
+                )
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Hashable<Key = $key_ty:ty> {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        impl<$($t_params)*> $self_ty
+        where $($where_clause)* {
+            #[doc(hidden)]
+            fn __implem_hashable_key(&$slf) -> $key_ty {
+                $def
+            }
+        }
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::hash::Hash for $self_ty
+        where $($where_clause)* {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                std::hash::Hash::hash(&self.__implem_hashable_key(), state)
+            }
+        }
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::cmp::PartialEq for $self_ty
+        where $($where_clause)* {
+            fn eq(&self, other: &Self) -> bool {
+                self.__implem_hashable_key() == other.__implem_hashable_key()
+            }
+        }
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::cmp::Eq for $self_ty
+        where $($where_clause)* {}
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    // `Comparable` plus the `Hash` impl, `PartialEq` and `Eq` are only generated once.
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Comparable + Hashable<Key = $key_ty:ty> {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Comparable<Key = $key_ty> { |&$slf| $def }
+        }
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::hash::Hash for $self_ty
+        where $($where_clause)* {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                std::hash::Hash::hash(&self.__implem_comparable_key(), state)
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Future<Output = $out_ty:ty> {
+            |$slf:ident: Pin<&mut Self>, $cx:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::future::Future for $self_ty
+        where $($where_clause)* {
+            type Output = $out_ty;
+            fn poll(
+                $slf: std::pin::Pin<&mut Self>,
+                $cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<$out_ty> {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        IntoFuture<Output = $out_ty:ty, IntoFuture = $fut_ty:ty> {
+            |$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::future::IntoFuture for $self_ty
+        where $($where_clause)* {
+            type Output = $out_ty;
+            type IntoFuture = $fut_ty;
+            fn into_future($slf) -> $fut_ty {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Wake {
+            |$slf:ident: Arc<Self>| $def:expr
+            $(
+                , |$slf_ref:ident: &Arc<Self>| $def_ref:expr
+            )?
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::task::Wake for $self_ty
+        where $($where_clause)* {
+            fn wake($slf: std::sync::Arc<Self>) {
+                $def
+            }
+            $(
+                fn wake_by_ref($slf_ref: &std::sync::Arc<Self>) {
+                    $def_ref
+                }
+            )?
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Termination {
+            |$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::process::Termination for $self_ty
+        where $($where_clause)* {
+            fn report($slf) -> std::process::ExitCode {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ErrorType {
+            |&$slf_display:ident, $fmt_display:pat| $def_display:expr,
+            |&$slf_debug:ident, $fmt_debug:pat| $def_debug:expr
+            $(
+                , |&$slf:ident| $def:expr
+            )?
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Display { |&$slf_display, $fmt_display| $def_display }
+            $(#[$attr])*
+            Debug { |&$slf_debug, $fmt_debug| $def_debug }
+            $(#[$attr])*
+            Error { $( |&$slf| $def )? }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Transparent { String $(,)? }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Transparent { String, str }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Transparent { $inner_ty:ty, $borrowed_ty:ty $(,)? }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            From<&$borrowed_ty> { |borrowed| Self(borrowed.into()) }
+            $(#[$attr])*
+            impl std::convert::AsRef<$borrowed_ty> {
+                fn as_ref(&self) -> &$borrowed_ty {
+                    std::convert::AsRef::as_ref(&self.0)
+                }
+            }
+            $(#[$attr])*
+            impl std::borrow::Borrow<$borrowed_ty> {
+                fn borrow(&self) -> &$borrowed_ty {
+                    std::borrow::Borrow::borrow(&self.0)
+                }
+            }
+            $(#[$attr])*
+            Transparent { $inner_ty }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Transparent { $inner_ty:ty $(,)? }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Display { |&self, fmt| std::fmt::Display::fmt(&self.0, fmt) }
+            $(#[$attr])*
+            Debug { |&self, fmt| std::fmt::Debug::fmt(&self.0, fmt) }
+            $(#[$attr])*
+            Deref<Target = $inner_ty> { |&self| &self.0 }
+            $(#[$attr])*
+            DerefMut { |&mut self| &mut self.0 }
+            $(#[$attr])*
+            From<$inner_ty> { |inner| Self(inner) }
+            $(#[$attr])*
+            impl std::convert::AsRef<$inner_ty> {
+                fn as_ref(&self) -> &$inner_ty {
+                    &self.0
+                }
+            }
+            $(#[$attr])*
+            impl std::borrow::Borrow<$inner_ty> {
+                fn borrow(&self) -> &$inner_ty {
+                    &self.0
+                }
+            }
+            $(#[$attr])*
+            PartialEq { |&self, other| self.0 == other.0 }
+            $(#[$attr])*
+            impl std::cmp::Eq {}
+            $(#[$attr])*
+            impl std::hash::Hash {
+                fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                    std::hash::Hash::hash(&self.0, state)
+                }
+            }
+            $(#[$attr])*
+            Clone { |&self| Self(std::clone::Clone::clone(&self.0)) }
+            $(#[$attr])*
+            Default { || Self(std::default::Default::default()) }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        impl $trait_path:path {
+            $($items:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> $trait_path for $self_ty
+        where $($where_clause)* {
+            $($items)*
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        const $($stuff:tt)*
+    } => {
+        $crate::internal_nightly! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            const $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FnOnce $($stuff:tt)*
+    } => {
+        $crate::internal_nightly! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            FnOnce $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FnMut $($stuff:tt)*
+    } => {
+        $crate::internal_nightly! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            FnMut $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Fn $($stuff:tt)*
+    } => {
+        $crate::internal_nightly! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Fn $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Try $($stuff:tt)*
+    } => {
+        $crate::internal_nightly! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Try $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FromResidual $($stuff:tt)*
+    } => {
+        $crate::internal_nightly! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            FromResidual $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Step $($stuff:tt)*
+    } => {
+        $crate::internal_nightly! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Step $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Serialize $($stuff:tt)*
+    } => {
+        $crate::internal_serde! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Serialize $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Deserialize $($stuff:tt)*
+    } => {
+        $crate::internal_serde! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Deserialize $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        SerializeViaDisplay $($stuff:tt)*
+    } => {
+        $crate::internal_serde! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            SerializeViaDisplay $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DeserializeViaFromStr $($stuff:tt)*
+    } => {
+        $crate::internal_serde! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            DeserializeViaFromStr $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        SerdeViaStr $($stuff:tt)*
+    } => {
+        $crate::internal_serde! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            SerdeViaStr $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Stream $($stuff:tt)*
+    } => {
+        $crate::internal_futures! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Stream $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Sink $($stuff:tt)*
+    } => {
+        $crate::internal_futures! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Sink $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        AsyncRead $($stuff:tt)*
+    } => {
+        $crate::internal_tokio! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            AsyncRead $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        AsyncWrite $($stuff:tt)*
+    } => {
+        $crate::internal_tokio! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            AsyncWrite $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        IntoParallelIterator $($stuff:tt)*
+    } => {
+        $crate::internal_rayon! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            IntoParallelIterator $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Zero $($stuff:tt)*
+    } => {
+        $crate::internal_num! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Zero $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        One $($stuff:tt)*
+    } => {
+        $crate::internal_num! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            One $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FromPrimitive $($stuff:tt)*
+    } => {
+        $crate::internal_num! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            FromPrimitive $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ToPrimitive $($stuff:tt)*
+    } => {
+        $crate::internal_num! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            ToPrimitive $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Arbitrary { $($stuff:tt)* }
+        $($tail:tt)*
+    } => {
+        $crate::internal_quickcheck! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Arbitrary { $($stuff)* }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ProptestArbitrary $($stuff:tt)*
+    } => {
+        $crate::internal_proptest! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            ProptestArbitrary $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Arbitrary<$lt:lifetime> $($stuff:tt)*
+    } => {
+        $crate::internal_arbitrary! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Arbitrary<$lt> $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        RandStandard $($stuff:tt)*
+    } => {
+        $crate::internal_rand! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            RandStandard $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Buf $($stuff:tt)*
+    } => {
+        $crate::internal_bytes! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Buf $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        unsafe BufMut $($stuff:tt)*
+    } => {
+        $crate::internal_bytes! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            unsafe BufMut $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        BufMut $($stuff:tt)*
+    } => {
+        $crate::internal_bytes! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            BufMut $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ValueEnum $($stuff:tt)*
+    } => {
+        $crate::internal_clap! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            ValueEnum $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        AbsDiffEq $($stuff:tt)*
+    } => {
+        $crate::internal_approx! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            AbsDiffEq $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        RelativeEq $($stuff:tt)*
+    } => {
+        $crate::internal_approx! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            RelativeEq $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Equivalent $($stuff:tt)*
+    } => {
+        $crate::internal_equivalent! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Equivalent $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        BorshSerialize $($stuff:tt)*
+    } => {
+        $crate::internal_borsh! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            BorshSerialize $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        BorshDeserialize $($stuff:tt)*
+    } => {
+        $crate::internal_borsh! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            BorshDeserialize $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        uDisplay $($stuff:tt)*
+    } => {
+        $crate::internal_ufmt! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            uDisplay $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        uDebug $($stuff:tt)*
+    } => {
+        $crate::internal_ufmt! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            uDebug $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Format $($stuff:tt)*
+    } => {
+        $crate::internal_defmt! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Format $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Zeroize $($stuff:tt)*
+    } => {
+        $crate::internal_zeroize! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            Zeroize $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ZeroizeOnDrop $($stuff:tt)*
+    } => {
+        $crate::internal_zeroize! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            ZeroizeOnDrop $($stuff)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        AsRawFd {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[cfg(unix)]
+        #[automatically_derived]
+        impl<$($t_params)*> std::os::fd::AsRawFd for $self_ty
+        where $($where_clause)* {
+            fn as_raw_fd(&$slf) -> std::os::fd::RawFd {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        AsFd {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[cfg(unix)]
+        #[automatically_derived]
+        impl<$($t_params)*> std::os::fd::AsFd for $self_ty
+        where $($where_clause)* {
+            fn as_fd(&$slf) -> std::os::fd::BorrowedFd<'_> {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        unsafe GlobalAlloc {
+            |&$slf:ident, $layout:pat| $def:expr,
+            |&$slf_dealloc:ident, $ptr_dealloc:pat, $layout_dealloc:pat| $def_dealloc:expr
+            $(,
+                |&$slf_realloc:ident, $ptr_realloc:pat, $layout_realloc:pat, $new_size:pat|
+                $def_realloc:expr
+                $(,
+                    |&$slf_zeroed:ident, $layout_zeroed:pat| $def_zeroed:expr
+                )?
+            )?
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        unsafe impl<$($t_params)*> std::alloc::GlobalAlloc for $self_ty
+        where $($where_clause)* {
+            unsafe fn alloc(&$slf, $layout: std::alloc::Layout) -> *mut u8 {
+                $def
+            }
+            unsafe fn dealloc(
+                &$slf_dealloc, $ptr_dealloc: *mut u8, $layout_dealloc: std::alloc::Layout,
+            ) {
+                $def_dealloc
+            }
+            $(
+                unsafe fn realloc(
+                    &$slf_realloc,
+                    $ptr_realloc: *mut u8,
+                    $layout_realloc: std::alloc::Layout,
+                    $new_size: usize,
+                ) -> *mut u8 {
+                    $def_realloc
+                }
+                $(
+                    unsafe fn alloc_zeroed(
+                        &$slf_zeroed, $layout_zeroed: std::alloc::Layout,
+                    ) -> *mut u8 {
+                        $def_zeroed
+                    }
+                )?
+            )?
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        GlobalAlloc $($stuff:tt)*
+    } => {
+        compile_error! {
+            "implementing `GlobalAlloc` is unsafe, write `unsafe GlobalAlloc { ... }` to make the \
+            assertion explicit"
+        }
+    };
+
+    // Formatting definitions whose first parameter is not `&self`.
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident $(<Err = $err_ty:ty>)? {
+            $($label:ident:)? |$slf:ident, $($stuff:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! { @by_value $trait $slf }
+    };
+    { @by_value Display $s:ident } => { $crate::internal! { @receiver Display $s } };
+    { @by_value Debug $s:ident } => { $crate::internal! { @receiver Debug $s } };
+    { @by_value Binary $s:ident } => { $crate::internal! { @receiver Binary $s } };
+    { @by_value Octal $s:ident } => { $crate::internal! { @receiver Octal $s } };
+    { @by_value LowerHex $s:ident } => { $crate::internal! { @receiver LowerHex $s } };
+    { @by_value UpperHex $s:ident } => { $crate::internal! { @receiver UpperHex $s } };
+    { @by_value Pointer $s:ident } => { $crate::internal! { @receiver Pointer $s } };
+    { @by_value LowerExp $s:ident } => { $crate::internal! { @receiver LowerExp $s } };
+    { @by_value UpperExp $s:ident } => { $crate::internal! { @receiver UpperExp $s } };
+    { @by_value DisplayAlt $s:ident } => { $crate::internal! { @receiver DisplayAlt $s } };
+    { @by_value DisplayFromStr $s:ident } => { $crate::internal! { @receiver DisplayFromStr $s } };
+    { @by_value ErrorType $s:ident } => { $crate::internal! { @receiver ErrorType $s } };
+    { @by_value $trait:ident $slf:ident } => { $crate::internal! { @unknown_trait $trait } };
+    { @receiver $trait:ident self } => {
+        compile_error! { concat!(
+            "`", stringify!($trait), "` definitions take `|&self, ..|`: the method they define ",
+            "takes `&self`, so `self` cannot be bound by value; dereference it in the body to get ",
+            "a copy"
+        ) }
+    };
+    { @receiver $trait:ident $slf:ident } => {
+        compile_error! { concat!(
+            "`", stringify!($trait), "` definitions take `|&self, ..|`: the first parameter ",
+            "must be `&self`, got `", stringify!($slf), "`"
+        ) }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+
+        $unk:ident
+
+        $($stuff:tt)*
+    } => {
+        $crate::internal! { @unknown_trait $unk }
+    };
+    { @unknown_trait $unk:ident } => {
+        compile_error! { concat!(
+            "expected known trait, got `", stringify!($unk), "`\n",
+            "supported traits are:\n",
+            "- Display, Debug, Binary, Octal, LowerHex, UpperHex, Pointer, LowerExp, UpperExp\n",
+            "- DisplayViaDebug, DebugViaDisplay, DisplayAlt\n",
+            "- FmtWrite\n",
+            "- FromStr, DisplayFromStr, FromStrViaTryFrom\n",
+            "- From, TryFrom, Into, Default, Clone, ToOwned\n",
+            "- Deref, DerefMut, DerefStr, DerefSlice, Deref1\n",
+            "- Index, IndexMut, RangeBounds\n",
+            "- Add, Sub, Mul, Div, Rem, BitAnd, BitOr, BitXor, Shl, Shr\n",
+            "- Unpin, unsafe Send, unsafe Sync, UnwindSafe, RefUnwindSafe\n",
+            "- unsafe GlobalAlloc\n",
+            "- Iterator, FusedIterator\n",
+            "- Hasher, BuildHasher\n",
+            "- Read, Write, Seek (or IoSeek), BufRead (or IoBufRead)\n",
+            "- ToSocketAddrs\n",
+            "- PartialEq, Comparable, Hashable, Comparable + Hashable\n",
+            "- Future, IntoFuture, Wake\n",
+            "- Termination\n",
+            "- AsRawFd, AsFd (unix only)\n",
+            "- Error, ErrorType\n",
+            "- Transparent\n",
+            "- with the `serde` feature: Serialize, Deserialize, SerializeViaDisplay, \
+            DeserializeViaFromStr, SerdeViaStr\n",
+            "- with the `futures` feature: Stream, Sink\n",
+            "- with the `tokio-io` feature: AsyncRead, AsyncWrite\n",
+            "- with the `rayon` feature: IntoParallelIterator\n",
+            "- with the `num-traits` feature: Zero, One, FromPrimitive, ToPrimitive\n",
+            "- with the `quickcheck` feature: Arbitrary\n",
+            "- with the `proptest` feature: ProptestArbitrary\n",
+            "- with the `arbitrary` feature: Arbitrary<'a>\n",
+            "- with the `rand` feature: RandStandard\n",
+            "- with the `bytes` feature: Buf, unsafe BufMut\n",
+            "- with the `clap` feature: ValueEnum\n",
+            "- with the `approx` feature: AbsDiffEq, RelativeEq\n",
+            "- with the `equivalent` feature: Equivalent\n",
+            "- with the `borsh` feature: BorshSerialize, BorshDeserialize\n",
+            "- with the `ufmt` feature: uDisplay, uDebug\n",
+            "- with the `defmt` feature: Format\n",
+            "- with the `zeroize` feature: Zeroize, ZeroizeOnDrop\n",
+            "- with the `nightly` feature: FnOnce, FnMut, Fn, Try, FromResidual, Step, and ",
+            "`const` From, Into, Default, Deref, DerefMut\n",
+            "- any other trait as `impl MyTrait { ... }`",
+        ) }
+    };
+
+    {} => {};
+    { @
+        ($($t_params:tt)*)
+        ($($where_clause:tt)*)
+        ($self_ty:ty)
+    } => {};
+}
+
+/// Handles trait definitions that are only available with the `nightly` feature.
+#[cfg(feature = "nightly")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_nightly {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        const From<$src_ty:ty> {
+            |$src:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> const std::convert::From<$src_ty> for $self_ty
+        where $($where_clauses)* {
+            fn from($src: $src_ty) -> Self {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        const Into<$tgt_ty:ty> {
+            |$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> const std::convert::Into<$tgt_ty> for $self_ty
+        where $($where_clauses)* {
+            fn into($slf) -> $tgt_ty {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clauses:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        const Default {
+            || $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> const std::default::Default for $self_ty
+        where $($where_clauses)* {
+            fn default() -> Self {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clauses)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        const Deref<Target = $tgt_ty:ty> {
+            |&$slf:ident| $def:expr,
+            |&mut $slf_mut:ident| $def_mut:expr
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            const Deref<Target = $tgt_ty> { |&$slf| $def }
+            $(#[$attr])*
+            const DerefMut { |&mut $slf_mut| $def_mut }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        const Deref<Target = $tgt_ty:ty> {
+            |&$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> const std::ops::Deref for $self_ty
+        where $($where_clause)* {
+            type Target = $tgt_ty;
+            fn deref(&$slf) -> &$tgt_ty {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        const DerefMut {
+            |&mut $slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> const std::ops::DerefMut for $self_ty
+        where $($where_clause)* {
+            fn deref_mut(&mut $slf) -> &mut <Self as std::ops::Deref>::Target {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FnOnce<$args_ty:ty, Output = $out_ty:ty> {
+            |$slf:ident, $args:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::FnOnce<$args_ty> for $self_ty
+        where $($where_clause)* {
+            type Output = $out_ty;
+            extern "rust-call" fn call_once($slf, $args: $args_ty) -> $out_ty {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FnMut<$args_ty:ty> {
+            |&mut $slf:ident, $args:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::FnMut<$args_ty> for $self_ty
+        where $($where_clause)* {
+            extern "rust-call" fn call_mut(&mut $slf, $args: $args_ty) -> Self::Output {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Fn<$args_ty:ty> {
+            |&$slf:ident, $args:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::Fn<$args_ty> for $self_ty
+        where $($where_clause)* {
+            extern "rust-call" fn call(&$slf, $args: $args_ty) -> Self::Output {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Try<Output = $out_ty:ty, Residual = $res_ty:ty> {
+            |$output:pat| $def_output:expr,
+            |$slf:ident| $def_branch:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::Try for $self_ty
+        where $($where_clause)* {
+            type Output = $out_ty;
+            type Residual = $res_ty;
+            fn from_output($output: $out_ty) -> Self {
+                $def_output
+            }
+            fn branch($slf) -> std::ops::ControlFlow<$res_ty, $out_ty> {
+                $def_branch
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FromResidual<$res_ty:ty> {
+            |$residual:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::FromResidual<$res_ty> for $self_ty
+        where $($where_clause)* {
+            fn from_residual($residual: $res_ty) -> Self {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FromResidual {
+            |$residual:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::FromResidual for $self_ty
+        where $($where_clause)* {
+            fn from_residual($residual: <Self as std::ops::Try>::Residual) -> Self {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Step {
+            $($methods:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::iter::Step for $self_ty
+        where $($where_clause)* {
+            $crate::internal_nightly! { @step_methods $($methods)* }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    {
+        @step_methods steps_between: |$start:pat, $end:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn steps_between($start: &Self, $end: &Self) -> (usize, Option<usize>) {
+            $def
+        }
+        $crate::internal_nightly! { @step_methods $($($rest)*)? }
+    };
+    {
+        @step_methods forward_checked: |$start:pat, $count:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn forward_checked($start: Self, $count: usize) -> Option<Self> {
+            $def
+        }
+        $crate::internal_nightly! { @step_methods $($($rest)*)? }
+    };
+    {
+        @step_methods backward_checked: |$start:pat, $count:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn backward_checked($start: Self, $count: usize) -> Option<Self> {
+            $def
+        }
+        $crate::internal_nightly! { @step_methods $($($rest)*)? }
+    };
+    { @step_methods } => {};
+    { @step_methods $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `Step` method `", stringify!($label), "`, expected\n",
+            "- steps_between: |start, end| ...\n",
+            "- forward_checked: |start, count| ...\n",
+            "- backward_checked: |start, count| ...",
+        ) }
+    };
+}
+
+/// Reports trait definitions that require the `nightly` feature.
+#[cfg(not(feature = "nightly"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_nightly {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        const $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "`const ", stringify!($trait), "` requires the `nightly` feature of `implem`"
+        ) }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `nightly` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `serde` feature.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_serde {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Serialize {
+            |&$slf:ident, $serializer:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::serde::Serialize for $self_ty
+        where $($where_clause)* {
+            fn serialize<__S>(&$slf, $serializer: __S) -> std::result::Result<__S::Ok, __S::Error>
+            where __S: ::serde::Serializer {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Deserialize {
+            |$deserializer:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<'de, $($t_params)*> ::serde::Deserialize<'de> for $self_ty
+        where $($where_clause)* {
+            fn deserialize<__D>($deserializer: __D) -> std::result::Result<Self, __D::Error>
+            where __D: ::serde::Deserializer<'de> {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        SerializeViaDisplay {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::serde::Serialize for $self_ty
+        where $($where_clause)* {
+            fn serialize<__S>(&self, serializer: __S) -> std::result::Result<__S::Ok, __S::Error>
+            where __S: ::serde::Serializer {
+                serializer.collect_str(self)
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        DeserializeViaFromStr {}
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<'de, $($t_params)*> ::serde::Deserialize<'de> for $self_ty
+        where $($where_clause)* {
+            fn deserialize<__D>(deserializer: __D) -> std::result::Result<Self, __D::Error>
+            where __D: ::serde::Deserializer<'de> {
+                let s = <std::string::String as ::serde::Deserialize>::deserialize(deserializer)?;
+                s.parse().map_err(::serde::de::Error::custom)
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        SerdeViaStr {}
+        $($tail:tt)*
+    } => {
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            SerializeViaDisplay {}
+            $(#[$attr])*
+            DeserializeViaFromStr {}
+            $($tail)*
+        }
+    };
+}
+
+/// Reports trait definitions that require the `serde` feature.
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_serde {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `serde` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `futures` feature.
+#[cfg(feature = "futures")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_futures {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Stream<Item = $item_ty:ty> {
+            |$slf:ident: Pin<&mut Self>, $cx:pat| $def:expr
+            $(, |&$slf_hint:ident| $def_hint:expr)?
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::futures_core::Stream for $self_ty
+        where $($where_clause)* {
+            type Item = $item_ty;
+            fn poll_next(
+                $slf: std::pin::Pin<&mut Self>,
+                $cx: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Option<$item_ty>> {
+                $def
+            }
+            $(
+                fn size_hint(&$slf_hint) -> (usize, Option<usize>) {
+                    $def_hint
+                }
+            )?
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Sink<$item_ty:ty, Error = $err_ty:ty> {
+            $($methods:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::futures_sink::Sink<$item_ty> for $self_ty
+        where $($where_clause)* {
+            type Error = $err_ty;
+            $crate::internal_futures! { @sink_methods ($item_ty) $($methods)* }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @sink_methods ($item_ty:ty)
+        poll_ready: |$slf:ident: Pin<&mut Self>, $cx:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn poll_ready(
+            $slf: std::pin::Pin<&mut Self>,
+            $cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::result::Result<(), Self::Error>> {
+            $def
+        }
+        $crate::internal_futures! { @sink_methods ($item_ty) $($($rest)*)? }
+    };
+    { @sink_methods ($item_ty:ty)
+        start_send: |$slf:ident: Pin<&mut Self>, $item:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn start_send(
+            $slf: std::pin::Pin<&mut Self>,
+            $item: $item_ty,
+        ) -> std::result::Result<(), Self::Error> {
+            $def
+        }
+        $crate::internal_futures! { @sink_methods ($item_ty) $($($rest)*)? }
+    };
+    { @sink_methods ($item_ty:ty)
+        poll_flush: |$slf:ident: Pin<&mut Self>, $cx:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn poll_flush(
+            $slf: std::pin::Pin<&mut Self>,
+            $cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::result::Result<(), Self::Error>> {
+            $def
+        }
+        $crate::internal_futures! { @sink_methods ($item_ty) $($($rest)*)? }
+    };
+    { @sink_methods ($item_ty:ty)
+        poll_close: |$slf:ident: Pin<&mut Self>, $cx:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn poll_close(
+            $slf: std::pin::Pin<&mut Self>,
+            $cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::result::Result<(), Self::Error>> {
+            $def
+        }
+        $crate::internal_futures! { @sink_methods ($item_ty) $($($rest)*)? }
+    };
+    { @sink_methods ($item_ty:ty) } => {};
+    { @sink_methods ($item_ty:ty) $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `Sink` method `", stringify!($label), "`, expected\n",
+            "- poll_ready: |self: Pin<&mut Self>, cx| ...\n",
+            "- start_send: |self: Pin<&mut Self>, item| ...\n",
+            "- poll_flush: |self: Pin<&mut Self>, cx| ...\n",
+            "- poll_close: |self: Pin<&mut Self>, cx| ...",
+        ) }
+    };
+}
+
+/// Reports trait definitions that require the `futures` feature.
+#[cfg(not(feature = "futures"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_futures {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `futures` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `tokio-io` feature.
+#[cfg(feature = "tokio-io")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_tokio {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        AsyncRead {
+            |$slf:ident: Pin<&mut Self>, $cx:pat, $buf:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::tokio::io::AsyncRead for $self_ty
+        where $($where_clause)* {
+            fn poll_read(
+                $slf: std::pin::Pin<&mut Self>,
+                $cx: &mut std::task::Context<'_>,
+                $buf: &mut ::tokio::io::ReadBuf<'_>,
+            ) -> std::task::Poll<std::io::Result<()>> {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        AsyncWrite {
+            $($methods:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::tokio::io::AsyncWrite for $self_ty
+        where $($where_clause)* {
+            $crate::internal_tokio! { @async_write_methods $($methods)* }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @async_write_methods
+        poll_write: |$slf:ident: Pin<&mut Self>, $cx:pat, $buf:pat| $def:expr
+        $(, $($rest:tt)*)?
+    } => {
+        fn poll_write(
+            $slf: std::pin::Pin<&mut Self>,
+            $cx: &mut std::task::Context<'_>,
+            $buf: &[u8],
+        ) -> std::task::Poll<std::io::Result<usize>> {
+            $def
+        }
+        $crate::internal_tokio! { @async_write_methods $($($rest)*)? }
+    };
+    { @async_write_methods
+        poll_flush: |$slf:ident: Pin<&mut Self>, $cx:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn poll_flush(
+            $slf: std::pin::Pin<&mut Self>,
+            $cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            $def
+        }
+        $crate::internal_tokio! { @async_write_methods $($($rest)*)? }
+    };
+    { @async_write_methods
+        poll_shutdown: |$slf:ident: Pin<&mut Self>, $cx:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn poll_shutdown(
+            $slf: std::pin::Pin<&mut Self>,
+            $cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<std::io::Result<()>> {
+            $def
+        }
+        $crate::internal_tokio! { @async_write_methods $($($rest)*)? }
+    };
+    { @async_write_methods } => {};
+    { @async_write_methods $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `AsyncWrite` method `", stringify!($label), "`, expected\n",
+            "- poll_write: |self: Pin<&mut Self>, cx, buf| ...\n",
+            "- poll_flush: |self: Pin<&mut Self>, cx| ...\n",
+            "- poll_shutdown: |self: Pin<&mut Self>, cx| ...",
+        ) }
+    };
+}
+
+/// Reports trait definitions that require the `tokio-io` feature.
+#[cfg(not(feature = "tokio-io"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_tokio {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `tokio-io` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `rayon` feature.
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_rayon {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        IntoParallelIterator<Item = $item_ty:ty, Iter = $iter_ty:ty> {
+            |$slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::rayon::iter::IntoParallelIterator for $self_ty
+        where $($where_clause)* {
+            type Item = $item_ty;
+            type Iter = $iter_ty;
+            $crate::internal_rayon! { @into_par_iter_fn ($slf) $slf ($iter_ty) $def }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    // `self` is hygienic, keep the user's token when the receiver is called `self`.
+    { @into_par_iter_fn ($slf:ident) self ($iter_ty:ty) $def:expr } => {
+        fn into_par_iter($slf) -> $iter_ty {
+            $def
+        }
+    };
+    { @into_par_iter_fn ($slf:ident) $other:ident ($iter_ty:ty) $def:expr } => {
+        fn into_par_iter(self) -> $iter_ty {
+            let $slf = self;
+            $def
+        }
+    };
+}
+
+/// Reports trait definitions that require the `rayon` feature.
+#[cfg(not(feature = "rayon"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_rayon {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `rayon` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `num-traits` feature.
+#[cfg(feature = "num-traits")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_num {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Zero {
+            || $def_zero:expr,
+            |&$slf:ident| $def_is_zero:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::num_traits::Zero for $self_ty
+        where $($where_clause)* {
+            fn zero() -> Self {
+                $def_zero
+            }
+            fn is_zero(&$slf) -> bool {
+                $def_is_zero
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        One {
+            || $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::num_traits::One for $self_ty
+        where $($where_clause)* {
+            fn one() -> Self {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        FromPrimitive {
+            $($methods:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::num_traits::FromPrimitive for $self_ty
+        where $($where_clause)* {
+            $crate::internal_num! { @from_primitive_methods $($methods)* }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ToPrimitive {
+            $($methods:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::num_traits::ToPrimitive for $self_ty
+        where $($where_clause)* {
+            $crate::internal_num! { @to_primitive_methods $($methods)* }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @from_primitive_methods from_i64: |$n:pat| $def:expr $(, $($rest:tt)*)? } => {
+        fn from_i64($n: i64) -> Option<Self> {
+            $def
+        }
+        $crate::internal_num! { @from_primitive_methods $($($rest)*)? }
+    };
+    { @from_primitive_methods from_u64: |$n:pat| $def:expr $(, $($rest:tt)*)? } => {
+        fn from_u64($n: u64) -> Option<Self> {
+            $def
+        }
+        $crate::internal_num! { @from_primitive_methods $($($rest)*)? }
+    };
+    { @from_primitive_methods from_f64: |$n:pat| $def:expr $(, $($rest:tt)*)? } => {
+        fn from_f64($n: f64) -> Option<Self> {
+            $def
+        }
+        $crate::internal_num! { @from_primitive_methods $($($rest)*)? }
+    };
+    { @from_primitive_methods } => {};
+    { @from_primitive_methods $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `FromPrimitive` method `", stringify!($label), "`, expected\n",
+            "- from_i64: |n| ...\n",
+            "- from_u64: |n| ...\n",
+            "- from_f64: |n| ...",
+        ) }
+    };
+    { @to_primitive_methods to_i64: |&$slf:ident| $def:expr $(, $($rest:tt)*)? } => {
+        fn to_i64(&$slf) -> Option<i64> {
+            $def
+        }
+        $crate::internal_num! { @to_primitive_methods $($($rest)*)? }
+    };
+    { @to_primitive_methods to_u64: |&$slf:ident| $def:expr $(, $($rest:tt)*)? } => {
+        fn to_u64(&$slf) -> Option<u64> {
+            $def
+        }
+        $crate::internal_num! { @to_primitive_methods $($($rest)*)? }
+    };
+    { @to_primitive_methods to_f64: |&$slf:ident| $def:expr $(, $($rest:tt)*)? } => {
+        fn to_f64(&$slf) -> Option<f64> {
+            $def
+        }
+        $crate::internal_num! { @to_primitive_methods $($($rest)*)? }
+    };
+    { @to_primitive_methods } => {};
+    { @to_primitive_methods $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `ToPrimitive` method `", stringify!($label), "`, expected\n",
+            "- to_i64: |&self| ...\n",
+            "- to_u64: |&self| ...\n",
+            "- to_f64: |&self| ...",
+        ) }
+    };
+}
+
+/// Reports trait definitions that require the `num-traits` feature.
+#[cfg(not(feature = "num-traits"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_num {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `num-traits` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `quickcheck` feature.
+#[cfg(feature = "quickcheck")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_quickcheck {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Arbitrary {
+            |$g:pat| $def:expr
+            $(, |&$slf:ident| $def_shrink:expr)?
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::quickcheck::Arbitrary for $self_ty
+        where $($where_clause)* {
+            fn arbitrary($g: &mut ::quickcheck::Gen) -> Self {
+                $def
+            }
+            $(
+                fn shrink(&$slf) -> std::boxed::Box<dyn std::iter::Iterator<Item = Self>> {
+                    $def_shrink
+                }
+            )?
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+}
+
+/// Reports trait definitions that require the `quickcheck` feature.
+#[cfg(not(feature = "quickcheck"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_quickcheck {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `quickcheck` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `proptest` feature.
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_proptest {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ProptestArbitrary<Strategy = $strategy_ty:ty> {
+            $($def:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $crate::internal_proptest! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $(#[$attr])*
+            ProptestArbitrary<Parameters = (), Strategy = $strategy_ty> {
+                $($def)*
+            }
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ProptestArbitrary<Parameters = $params_ty:ty, Strategy = $strategy_ty:ty> {
+            |$args:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::proptest::arbitrary::Arbitrary for $self_ty
+        where $($where_clause)* {
+            type Parameters = $params_ty;
+            type Strategy = $strategy_ty;
+            fn arbitrary_with($args: Self::Parameters) -> Self::Strategy {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+}
+
+/// Reports trait definitions that require the `proptest` feature.
+#[cfg(not(feature = "proptest"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_proptest {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `proptest` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `arbitrary` feature.
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_arbitrary {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Arbitrary<$lt:lifetime> {
+            |$u:pat| $def:expr
+            $(, |$depth:pat| $def_hint:expr)?
+            $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$lt, $($t_params)*> ::arbitrary::Arbitrary<$lt> for $self_ty
+        where $($where_clause)* {
+            fn arbitrary($u: &mut ::arbitrary::Unstructured<$lt>) -> ::arbitrary::Result<Self> {
+                $def
+            }
+            $(
+                fn size_hint($depth: usize) -> (usize, Option<usize>) {
+                    $def_hint
+                }
+            )?
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+}
+
+/// Reports trait definitions that require the `arbitrary` feature.
+#[cfg(not(feature = "arbitrary"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_arbitrary {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `arbitrary` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `rand` feature.
+#[cfg(feature = "rand")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_rand {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        RandStandard {
+            |$rng:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::rand::distributions::Distribution<$self_ty>
+        for ::rand::distributions::Standard
+        where $($where_clause)* {
+            fn sample<__R: ::rand::Rng + ?Sized>(&self, $rng: &mut __R) -> $self_ty {
+                $def
+            }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+}
+
+/// Reports trait definitions that require the `rand` feature.
+#[cfg(not(feature = "rand"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_rand {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `rand` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `bytes` feature.
+#[cfg(feature = "bytes")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_bytes {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Buf {
+            $($methods:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::bytes::Buf for $self_ty
+        where $($where_clause)* {
+            $crate::internal_bytes! { @buf_methods $($methods)* }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        unsafe BufMut {
+            $($methods:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        unsafe impl<$($t_params)*> ::bytes::BufMut for $self_ty
+        where $($where_clause)* {
+            $crate::internal_bytes! { @buf_mut_methods $($methods)* }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        BufMut $($stuff:tt)*
+    } => {
+        compile_error! {
+            "implementing `BufMut` is unsafe, write `unsafe BufMut { ... }` to make the assertion \
+            explicit"
+        }
+    };
+    { @buf_methods remaining: |&$slf:ident| $def:expr $(, $($rest:tt)*)? } => {
+        fn remaining(&$slf) -> usize {
+            $def
+        }
+        $crate::internal_bytes! { @buf_methods $($($rest)*)? }
+    };
+    { @buf_methods chunk: |&$slf:ident| $def:expr $(, $($rest:tt)*)? } => {
+        fn chunk(&$slf) -> &[u8] {
+            $def
+        }
+        $crate::internal_bytes! { @buf_methods $($($rest)*)? }
+    };
+    { @buf_methods advance: |&mut $slf:ident, $cnt:pat| $def:expr $(, $($rest:tt)*)? } => {
+        fn advance(&mut $slf, $cnt: usize) {
+            $def
+        }
+        $crate::internal_bytes! { @buf_methods $($($rest)*)? }
+    };
+    { @buf_methods } => {};
+    { @buf_methods $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `Buf` method `", stringify!($label), "`, expected\n",
+            "- remaining: |&self| ...\n",
+            "- chunk: |&self| ...\n",
+            "- advance: |&mut self, cnt| ...",
+        ) }
+    };
+    { @buf_mut_methods remaining_mut: |&$slf:ident| $def:expr $(, $($rest:tt)*)? } => {
+        fn remaining_mut(&$slf) -> usize {
+            $def
+        }
+        $crate::internal_bytes! { @buf_mut_methods $($($rest)*)? }
+    };
+    { @buf_mut_methods
+        advance_mut: |&mut $slf:ident, $cnt:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        unsafe fn advance_mut(&mut $slf, $cnt: usize) {
+            $def
+        }
+        $crate::internal_bytes! { @buf_mut_methods $($($rest)*)? }
+    };
+    { @buf_mut_methods chunk_mut: |&mut $slf:ident| $def:expr $(, $($rest:tt)*)? } => {
+        fn chunk_mut(&mut $slf) -> &mut ::bytes::buf::UninitSlice {
+            $def
+        }
+        $crate::internal_bytes! { @buf_mut_methods $($($rest)*)? }
+    };
+    { @buf_mut_methods } => {};
+    { @buf_mut_methods $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `BufMut` method `", stringify!($label), "`, expected\n",
+            "- remaining_mut: |&self| ...\n",
+            "- advance_mut: |&mut self, cnt| ... (an `unsafe fn`)\n",
+            "- chunk_mut: |&mut self| ...",
+        ) }
+    };
+}
+
+/// Reports trait definitions that require the `bytes` feature.
+#[cfg(not(feature = "bytes"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_bytes {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        unsafe $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `bytes` feature of `implem`"
+        ) }
+    };
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `bytes` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `clap` feature.
+#[cfg(feature = "clap")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_clap {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        ValueEnum {
+            $($methods:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::clap::ValueEnum for $self_ty
+        where $($where_clause)* {
+            $crate::internal_clap! { @value_enum_methods $($methods)* }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @value_enum_methods value_variants: || $def:expr $(, $($rest:tt)*)? } => {
+        fn value_variants<'a>() -> &'a [Self] {
+            $def
+        }
+        $crate::internal_clap! { @value_enum_methods $($($rest)*)? }
+    };
+    { @value_enum_methods to_possible_value: |&$slf:ident| $def:expr $(, $($rest:tt)*)? } => {
+        fn to_possible_value(&$slf) -> Option<::clap::builder::PossibleValue> {
+            $def
+        }
+        $crate::internal_clap! { @value_enum_methods $($($rest)*)? }
+    };
+    { @value_enum_methods } => {};
+    { @value_enum_methods $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `ValueEnum` method `", stringify!($label), "`, expected\n",
+            "- value_variants: || ...\n",
+            "- to_possible_value: |&self| ...",
+        ) }
+    };
+}
 
-/// The whole point, see [crate-level documentation][doc] for details.
-///
-/// [doc]: ./index.html (crate-level documentation)
+/// Reports trait definitions that require the `clap` feature.
+#[cfg(not(feature = "clap"))]
+#[doc(hidden)]
 #[macro_export]
-macro_rules! implem {
-    {
-        $(
-            impl ($($t_params:tt)*)
-        )?
-        for $self_ty:ty
-        $(
-            where ($($where_clauses:tt)*)
-        )? {
-            $($stuff:tt)*
-        }
+macro_rules! internal_clap {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `clap` feature of `implem`"
+        ) }
+    };
+}
 
+/// Handles trait definitions that are only available with the `approx` feature.
+#[cfg(feature = "approx")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_approx {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        AbsDiffEq<Epsilon = $eps_ty:ty> {
+            $($methods:tt)*
+        }
         $($tail:tt)*
     } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::approx::AbsDiffEq for $self_ty
+        where $($where_clause)* {
+            type Epsilon = $eps_ty;
+            $crate::internal_approx! { @abs_diff_eq_methods $($methods)* }
+        }
         $crate::internal! {
-            @(
-                $( $($t_params)* )?
-            )(
-                $( $($where_clauses)* )?
-            )(
-                $self_ty
-            )
-            $($stuff)*
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
         }
-        $crate::implem! { $($tail)* }
     };
-    {} => {};
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        RelativeEq {
+            $($methods:tt)*
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::approx::RelativeEq for $self_ty
+        where $($where_clause)* {
+            $crate::internal_approx! { @relative_eq_methods $($methods)* }
+        }
+        $crate::internal! {
+            @($($t_params)*)($($where_clause)*)($self_ty)
+            $($tail)*
+        }
+    };
+    { @abs_diff_eq_methods default_epsilon: || $def:expr $(, $($rest:tt)*)? } => {
+        fn default_epsilon() -> Self::Epsilon {
+            $def
+        }
+        $crate::internal_approx! { @abs_diff_eq_methods $($($rest)*)? }
+    };
+    { @abs_diff_eq_methods
+        abs_diff_eq: |&$slf:ident, $other:pat, $eps:pat| $def:expr $(, $($rest:tt)*)?
+    } => {
+        fn abs_diff_eq(&$slf, $other: &Self, $eps: Self::Epsilon) -> bool {
+            $def
+        }
+        $crate::internal_approx! { @abs_diff_eq_methods $($($rest)*)? }
+    };
+    { @abs_diff_eq_methods } => {};
+    { @abs_diff_eq_methods $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `AbsDiffEq` method `", stringify!($label), "`, expected\n",
+            "- default_epsilon: || ...\n",
+            "- abs_diff_eq: |&self, other, epsilon| ...",
+        ) }
+    };
+    { @relative_eq_methods default_max_relative: || $def:expr $(, $($rest:tt)*)? } => {
+        fn default_max_relative() -> Self::Epsilon {
+            $def
+        }
+        $crate::internal_approx! { @relative_eq_methods $($($rest)*)? }
+    };
+    { @relative_eq_methods
+        relative_eq: |&$slf:ident, $other:pat, $eps:pat, $max_rel:pat| $def:expr
+        $(, $($rest:tt)*)?
+    } => {
+        fn relative_eq(
+            &$slf, $other: &Self, $eps: Self::Epsilon, $max_rel: Self::Epsilon,
+        ) -> bool {
+            $def
+        }
+        $crate::internal_approx! { @relative_eq_methods $($($rest)*)? }
+    };
+    { @relative_eq_methods } => {};
+    { @relative_eq_methods $label:ident $($stuff:tt)* } => {
+        compile_error! { concat!(
+            "unknown or malformed `RelativeEq` method `", stringify!($label), "`, expected\n",
+            "- default_max_relative: || ...\n",
+            "- relative_eq: |&self, other, epsilon, max_relative| ...",
+        ) }
+    };
 }
 
+/// Reports trait definitions that require the `approx` feature.
+#[cfg(not(feature = "approx"))]
 #[doc(hidden)]
 #[macro_export]
-macro_rules! internal {
+macro_rules! internal_approx {
     { @
         ( $($t_params:tt)* )
-        ( $($where_clauses:tt)* )
+        ( $($where_clause:tt)* )
         ($self_ty:ty)
-        Display {
-            |&$slf:ident, $fmt:pat| $def:expr
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `approx` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `equivalent` feature.
+#[cfg(feature = "equivalent")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_equivalent {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Equivalent<$key_ty:ty> {
+            |&$slf:ident, $key:pat| $def:expr $(,)?
         }
         $($tail:tt)*
     } => {
-        impl<$($t_params)*> std::fmt::Display for $self_ty
-        where $($where_clauses)* {
-            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::equivalent::Equivalent<$key_ty> for $self_ty
+        where $($where_clause)* {
+            fn equivalent(&$slf, $key: &$key_ty) -> bool {
                 $def
             }
         }
         $crate::internal! {
-            @($($t_params)*)($($where_clauses)*)($self_ty)
+            @($($t_params)*)($($where_clause)*)($self_ty)
             $($tail)*
         }
     };
+}
+
+/// Reports trait definitions that require the `equivalent` feature.
+#[cfg(not(feature = "equivalent"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_equivalent {
     { @
         ( $($t_params:tt)* )
-        ( $($where_clauses:tt)* )
+        ( $($where_clause:tt)* )
         ($self_ty:ty)
-        Debug {
-            |&$slf:ident, $fmt:pat| $def:expr
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `equivalent` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `borsh` feature.
+#[cfg(feature = "borsh")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_borsh {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        BorshSerialize {
+            |&$slf:ident, $writer:pat| $def:expr $(,)?
         }
         $($tail:tt)*
     } => {
-        impl<$($t_params)*> std::fmt::Debug for $self_ty
-        where $($where_clauses)* {
-            fn fmt(&$slf, $fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::borsh::BorshSerialize for $self_ty
+        where $($where_clause)* {
+            fn serialize<__W: std::io::Write>(&$slf, $writer: &mut __W) -> std::io::Result<()> {
                 $def
             }
         }
         $crate::internal! {
-            @($($t_params)*)($($where_clauses)*)($self_ty)
+            @($($t_params)*)($($where_clause)*)($self_ty)
             $($tail)*
         }
     };
     { @
         ( $($t_params:tt)* )
-        ( $($where_clauses:tt)* )
+        ( $($where_clause:tt)* )
         ($self_ty:ty)
-        From<$src_ty:ty> {
-            |$src:pat| $def:expr $(,)?
+        $(#[$attr:meta])*
+        BorshDeserialize {
+            |$reader:pat| $def:expr $(,)?
         }
         $($tail:tt)*
     } => {
-        impl<$($t_params)*> std::convert::From<$src_ty> for $self_ty
-        where $($where_clauses)* {
-            fn from($src: $src_ty) -> Self {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::borsh::BorshDeserialize for $self_ty
+        where $($where_clause)* {
+            fn deserialize_reader<__R: std::io::Read>($reader: &mut __R) -> std::io::Result<Self> {
                 $def
             }
         }
         $crate::internal! {
-            @($($t_params)*)($($where_clauses)*)($self_ty)
+            @($($t_params)*)($($where_clause)*)($self_ty)
             $($tail)*
         }
     };
+}
+
+/// Reports trait definitions that require the `borsh` feature.
+#[cfg(not(feature = "borsh"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_borsh {
     { @
         ( $($t_params:tt)* )
-        ( $($where_clauses:tt)* )
+        ( $($where_clause:tt)* )
         ($self_ty:ty)
-        Into<$tgt_ty:ty> {
-            |$slf:ident| $def:expr $(,)?
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `borsh` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `ufmt` feature.
+#[cfg(feature = "ufmt")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_ufmt {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        uDisplay {
+            |&$slf:ident, $fmt:pat| $def:expr $(,)?
         }
         $($tail:tt)*
     } => {
-        impl<$($t_params)*> std::convert::Into<$tgt_ty> for $self_ty
-        where $($where_clauses)* {
-            fn into($slf) -> $tgt_ty {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::ufmt::uDisplay for $self_ty
+        where $($where_clause)* {
+            fn fmt<__W>(
+                &$slf, $fmt: &mut ::ufmt::Formatter<'_, __W>,
+            ) -> std::result::Result<(), __W::Error>
+            where __W: ::ufmt::uWrite + ?Sized {
                 $def
             }
         }
         $crate::internal! {
-            @($($t_params)*)($($where_clauses)*)($self_ty)
+            @($($t_params)*)($($where_clause)*)($self_ty)
             $($tail)*
         }
     };
@@ -218,42 +8435,82 @@ macro_rules! internal {
         ( $($t_params:tt)* )
         ( $($where_clause:tt)* )
         ($self_ty:ty)
-        Deref<Target = $tgt_ty:ty> {
-            |&$slf:ident| $def:expr
-            $(
-                , |&mut $slf_mut:ident| $def_mut:expr
-            )?
-            $(,)?
+        $(#[$attr:meta])*
+        uDebug {
+            |&$slf:ident, $fmt:pat| $def:expr $(,)?
         }
         $($tail:tt)*
     } => {
-        impl<$($t_params)*> std::ops::Deref for $self_ty
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::ufmt::uDebug for $self_ty
         where $($where_clause)* {
-            type Target = $tgt_ty;
-            fn deref(&$slf) -> &$tgt_ty {
+            fn fmt<__W>(
+                &$slf, $fmt: &mut ::ufmt::Formatter<'_, __W>,
+            ) -> std::result::Result<(), __W::Error>
+            where __W: ::ufmt::uWrite + ?Sized {
                 $def
             }
         }
         $crate::internal! {
             @($($t_params)*)($($where_clause)*)($self_ty)
-            $(
-                DerefMut { |&mut $slf_mut| $def_mut }
-            )?
             $($tail)*
         }
     };
+    // Definitions whose first parameter is not `&self`.
     { @
         ( $($t_params:tt)* )
         ( $($where_clause:tt)* )
         ($self_ty:ty)
-        DerefMut {
-            |&mut $slf:ident| $def:expr $(,)?
+        $(#[$attr:meta])*
+        $trait:ident {
+            |$slf:ident, $($stuff:tt)*
         }
         $($tail:tt)*
     } => {
-        impl<$($t_params)*> std::ops::DerefMut for $self_ty
+        $crate::internal! { @receiver $trait $slf }
+    };
+}
+
+/// Reports trait definitions that require the `ufmt` feature.
+#[cfg(not(feature = "ufmt"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_ufmt {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `ufmt` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `defmt` feature.
+#[cfg(feature = "defmt")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_defmt {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Format {
+            |&$slf:ident, $fmt:pat| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::defmt::Format for $self_ty
         where $($where_clause)* {
-            fn deref_mut(&mut $slf) -> &mut <Self as std::ops::Deref>::Target {
+            fn format(&$slf, $fmt: ::defmt::Formatter<'_>) {
                 $def
             }
         }
@@ -262,31 +8519,65 @@ macro_rules! internal {
             $($tail)*
         }
     };
+    // Definitions whose first parameter is not `&self`.
     { @
         ( $($t_params:tt)* )
         ( $($where_clause:tt)* )
         ($self_ty:ty)
-        Index<$idx_ty:ty, Output = $out_ty:ty> {
-            |&$slf:ident, $idx:pat| $def:expr
-            $(
-                , |&mut $slf_mut:ident, $idx_mut:pat| $def_mut:expr
-            )?
-            $(,)?
+        $(#[$attr:meta])*
+        $trait:ident {
+            |$slf:ident, $($stuff:tt)*
         }
         $($tail:tt)*
     } => {
-        impl<$($t_params)*> std::ops::Index<$idx_ty> for $self_ty
+        $crate::internal! { @receiver $trait $slf }
+    };
+}
+
+/// Reports trait definitions that require the `defmt` feature.
+#[cfg(not(feature = "defmt"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_defmt {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        $trait:ident
+        $($stuff:tt)*
+    } => {
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `defmt` feature of `implem`"
+        ) }
+    };
+}
+
+/// Handles trait definitions that are only available with the `zeroize` feature.
+#[cfg(feature = "zeroize")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_zeroize {
+    { @
+        ( $($t_params:tt)* )
+        ( $($where_clause:tt)* )
+        ($self_ty:ty)
+        $(#[$attr:meta])*
+        Zeroize {
+            |&mut $slf:ident| $def:expr $(,)?
+        }
+        $($tail:tt)*
+    } => {
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::zeroize::Zeroize for $self_ty
         where $($where_clause)* {
-            type Output = $out_ty;
-            fn index(&$slf, $idx: $idx_ty) -> &Self::Output {
+            fn zeroize(&mut $slf) {
                 $def
             }
         }
         $crate::internal! {
             @($($t_params)*)($($where_clause)*)($self_ty)
-            $(
-                IndexMut<$idx_ty> { |&mut $slf_mut, $idx_mut| $def_mut }
-            )?
             $($tail)*
         }
     };
@@ -294,16 +8585,20 @@ macro_rules! internal {
         ( $($t_params:tt)* )
         ( $($where_clause:tt)* )
         ($self_ty:ty)
-        IndexMut<$idx_ty:ty> {
-            |&mut $slf:ident, $idx:pat| $def_mut:expr
-            $(,)?
-        }
+        $(#[$attr:meta])*
+        ZeroizeOnDrop {}
         $($tail:tt)*
     } => {
-        impl<$($t_params)*> std::ops::IndexMut<$idx_ty> for $self_ty
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> ::zeroize::ZeroizeOnDrop for $self_ty
+        where $($where_clause)* {}
+        $(#[$attr])*
+        #[automatically_derived]
+        impl<$($t_params)*> std::ops::Drop for $self_ty
         where $($where_clause)* {
-            fn index_mut(&mut $slf, $idx: $idx_ty) -> &mut Self::Output {
-                $def_mut
+            fn drop(&mut self) {
+                ::zeroize::Zeroize::zeroize(self)
             }
         }
         $crate::internal! {
@@ -311,25 +8606,23 @@ macro_rules! internal {
             $($tail)*
         }
     };
+}
 
+/// Reports trait definitions that require the `zeroize` feature.
+#[cfg(not(feature = "zeroize"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! internal_zeroize {
     { @
         ( $($t_params:tt)* )
         ( $($where_clause:tt)* )
         ($self_ty:ty)
-
-        $unk:ident
-
+        $(#[$attr:meta])*
+        $trait:ident
         $($stuff:tt)*
     } => {
-        compile_error!(concat!(
-            "expected known trait, got `", stringify!($unk), "`"
-        ))
+        compile_error! { concat!(
+            "trait `", stringify!($trait), "` requires the `zeroize` feature of `implem`"
+        ) }
     };
-
-    {} => {};
-    { @
-        ($($t_params:tt)*)
-        ($($where_clause:tt)*)
-        ($self_ty:ty)
-    } => {};
 }
@@ -0,0 +1,47 @@
+//! Tests the `approx` definitions, run with `cargo test --features approx`.
+
+#![cfg(feature = "approx")]
+
+use approx::{assert_abs_diff_eq, assert_relative_eq, AbsDiffEq, RelativeEq};
+use implem::implem;
+
+#[derive(Debug, PartialEq)]
+pub struct Meters(f64);
+implem! {
+    for Meters {
+        AbsDiffEq<Epsilon = f64> {
+            default_epsilon: || 1e-9,
+            abs_diff_eq: |&self, other, eps| self.0.abs_diff_eq(&other.0, eps),
+        }
+        RelativeEq {
+            relative_eq: |&self, other, eps, max_rel| self.0.relative_eq(&other.0, eps, max_rel),
+            default_max_relative: || 1e-9,
+        }
+    }
+}
+
+#[test]
+fn abs_diff_eq() {
+    assert_abs_diff_eq!(Meters(0.1 + 0.2), Meters(0.3));
+    assert_abs_diff_eq!(Meters(1.0), Meters(1.4), epsilon = 0.5);
+    assert!(Meters(1.0).abs_diff_ne(&Meters(1.0 + 1e-6), Meters::default_epsilon()));
+}
+
+#[test]
+fn relative_eq() {
+    assert_relative_eq!(Meters(1e6 + 1e-4), Meters(1e6));
+    assert_relative_eq!(Meters(100.0), Meters(101.0), max_relative = 0.01);
+    // Too far apart in absolute terms, close enough in relative terms.
+    assert!(Meters(1e6 + 1e-4).abs_diff_ne(&Meters(1e6), Meters::default_epsilon()));
+    assert!(Meters(1.0).relative_ne(
+        &Meters(1.0 + 1e-6),
+        Meters::default_epsilon(),
+        Meters::default_max_relative(),
+    ));
+}
+
+#[test]
+#[should_panic(expected = "assert_relative_eq!(Meters(1.0), Meters(1.1))")]
+fn relative_ne() {
+    assert_relative_eq!(Meters(1.0), Meters(1.1));
+}
@@ -0,0 +1,83 @@
+//! Tests the `arbitrary` definitions, run with `cargo test --features arbitrary`.
+
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use implem::implem;
+
+#[derive(Debug, PartialEq)]
+pub struct Frame {
+    len: u16,
+    flags: u8,
+}
+implem! {
+    for Frame {
+        Arbitrary<'a> {
+            |u| Ok(Self { len: u.arbitrary()?, flags: u.arbitrary()? }),
+            |depth| arbitrary::size_hint::and(u16::size_hint(depth), u8::size_hint(depth)),
+        }
+    }
+}
+
+/// A borrowed slice, the trait's lifetime appears in the self type.
+#[derive(Debug, PartialEq)]
+pub struct Chunk<'a>(&'a [u8]);
+implem! {
+    for Chunk<'a> {
+        Arbitrary<'a> {
+            |u| {
+                let len: u8 = u.arbitrary()?;
+                u.bytes(len as usize).map(Self)
+            }
+        }
+    }
+}
+
+/// Generic, with a where clause mentioning the trait's lifetime.
+#[derive(Debug, PartialEq)]
+pub struct Pair<T>(T, T);
+implem! {
+    impl(T) for Pair<T> where (T: Arbitrary<'a>) {
+        Arbitrary<'a> {
+            |u| Ok(Self(u.arbitrary()?, u.arbitrary()?)),
+            |depth| arbitrary::size_hint::and(T::size_hint(depth), T::size_hint(depth)),
+        }
+    }
+}
+
+#[test]
+fn from_bytes() {
+    let mut u = Unstructured::new(&[1, 0, 7, 42]);
+    assert_eq!(
+        Frame::arbitrary(&mut u).unwrap(),
+        Frame { len: 1, flags: 7 }
+    );
+    assert_eq!(u.len(), 1);
+
+    let data = [3, 10, 20, 30, 40];
+    let mut u = Unstructured::new(&data);
+    assert_eq!(Chunk::arbitrary(&mut u).unwrap(), Chunk(&[10, 20, 30]));
+    assert_eq!(u.len(), 1);
+    let mut u = Unstructured::new(&[3, 10]);
+    assert_eq!(
+        Chunk::arbitrary(&mut u),
+        Err(arbitrary::Error::NotEnoughData)
+    );
+
+    let mut u = Unstructured::new(&[1, 0, 2, 0]);
+    assert_eq!(Pair::<u16>::arbitrary(&mut u).unwrap(), Pair(1, 2));
+    // Missing bytes read as zeros.
+    let mut u = Unstructured::new(&[1, 0, 2, 7, 0]);
+    assert_eq!(
+        Pair::<Frame>::arbitrary(&mut u).unwrap(),
+        Pair(Frame { len: 1, flags: 2 }, Frame { len: 7, flags: 0 }),
+    );
+}
+
+#[test]
+fn size_hint() {
+    assert_eq!(Frame::size_hint(0), (3, Some(3)));
+    assert_eq!(Chunk::size_hint(0), (0, None));
+    assert_eq!(Pair::<u32>::size_hint(0), (8, Some(8)));
+    assert_eq!(Pair::<Frame>::size_hint(0), (6, Some(6)));
+}
@@ -0,0 +1,110 @@
+//! Tests the `borsh` definitions, run with `cargo test --features borsh`.
+
+#![cfg(feature = "borsh")]
+
+use implem::implem;
+
+#[derive(Debug, PartialEq)]
+pub struct Lamports(u64);
+implem! {
+    for Lamports {
+        BorshSerialize {
+            |&self, writer| self.0.serialize(writer)
+        }
+        BorshDeserialize {
+            |reader| Ok(Self(u64::deserialize_reader(reader)?))
+        }
+    }
+}
+
+/// Validates its tag on deserialization.
+#[derive(Debug, PartialEq)]
+pub struct Packet {
+    tag: u8,
+    payload: Vec<u8>,
+}
+implem! {
+    for Packet {
+        BorshSerialize {
+            |&self, writer| {
+                self.tag.serialize(writer)?;
+                self.payload.serialize(writer)
+            }
+        }
+        BorshDeserialize {
+            |reader| {
+                let tag = u8::deserialize_reader(reader)?;
+                if tag > 3 {
+                    return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "bad tag"));
+                }
+                let payload = Vec::deserialize_reader(reader)?;
+                Ok(Self { tag, payload })
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Tagged<T>(String, T);
+implem! {
+    impl(T) for Tagged<T> where (T: borsh::BorshSerialize + borsh::BorshDeserialize) {
+        BorshSerialize {
+            |&self, writer| {
+                self.0.serialize(writer)?;
+                self.1.serialize(writer)
+            }
+        }
+        BorshDeserialize {
+            |reader| Ok(Self(String::deserialize_reader(reader)?, T::deserialize_reader(reader)?))
+        }
+    }
+}
+
+#[test]
+fn lamports() {
+    let bytes = borsh::to_vec(&Lamports(42)).unwrap();
+    assert_eq!(bytes, 42u64.to_le_bytes());
+    assert_eq!(borsh::from_slice::<Lamports>(&bytes).unwrap(), Lamports(42));
+    assert!(borsh::from_slice::<Lamports>(&bytes[..7]).is_err());
+}
+
+#[test]
+fn vec_round_trip() {
+    let packet = Packet {
+        tag: 2,
+        payload: vec![7, 8, 9],
+    };
+    let bytes = borsh::to_vec(&packet).unwrap();
+    assert_eq!(bytes, [2, 3, 0, 0, 0, 7, 8, 9]);
+    assert_eq!(borsh::from_slice::<Packet>(&bytes).unwrap(), packet);
+
+    let bad = [4, 0, 0, 0, 0];
+    assert_eq!(
+        borsh::from_slice::<Packet>(&bad).unwrap_err().to_string(),
+        "bad tag",
+    );
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(borsh::from_slice::<Packet>(&trailing).is_err());
+
+    let packets = vec![
+        packet,
+        Packet {
+            tag: 0,
+            payload: vec![],
+        },
+    ];
+    let bytes = borsh::to_vec(&packets).unwrap();
+    assert_eq!(borsh::from_slice::<Vec<Packet>>(&bytes).unwrap(), packets);
+}
+
+#[test]
+fn generic() {
+    let tagged = Tagged("fee".to_string(), Lamports(5000));
+    let bytes = borsh::to_vec(&tagged).unwrap();
+    assert_eq!(bytes.len(), 4 + 3 + 8);
+    assert_eq!(
+        borsh::from_slice::<Tagged<Lamports>>(&bytes).unwrap(),
+        tagged
+    );
+}
@@ -0,0 +1,86 @@
+//! Tests the `bytes` definitions, run with `cargo test --features bytes`.
+
+#![cfg(feature = "bytes")]
+
+use bytes::{Buf, BufMut};
+use implem::implem;
+
+/// Reads at most `window` bytes per chunk.
+pub struct Frame {
+    data: Vec<u8>,
+    pos: usize,
+    window: usize,
+}
+implem! {
+    for Frame {
+        Buf {
+            remaining: |&self| self.data.len() - self.pos,
+            chunk: |&self| {
+                let end = self.data.len().min(self.pos + self.window);
+                &self.data[self.pos..end]
+            },
+            advance: |&mut self, cnt| self.pos += cnt,
+        }
+    }
+}
+
+/// Writes at most `window` bytes per chunk.
+pub struct Out {
+    data: Vec<u8>,
+    len: usize,
+    window: usize,
+    advances: usize,
+}
+implem! {
+    for Out {
+        unsafe BufMut {
+            chunk_mut: |&mut self| {
+                let end = self.data.len().min(self.len + self.window);
+                bytes::buf::UninitSlice::new(&mut self.data[self.len..end])
+            },
+            advance_mut: |&mut self, cnt| {
+                assert!(self.len + cnt <= self.data.len());
+                self.len += cnt;
+                self.advances += 1;
+            },
+            remaining_mut: |&self| self.data.len() - self.len,
+        }
+    }
+}
+
+#[test]
+fn get_u32() {
+    let mut frame = Frame {
+        data: vec![0, 0, 0, 1, 0, 0, 1, 0, 7],
+        pos: 0,
+        window: 3,
+    };
+    assert_eq!(frame.get_u32(), 1);
+    assert_eq!(frame.get_u32(), 256);
+    assert_eq!(frame.remaining(), 1);
+    assert_eq!(frame.get_u8(), 7);
+    assert!(!frame.has_remaining());
+}
+
+#[test]
+fn put_u32() {
+    let mut out = Out {
+        data: vec![0; 9],
+        len: 0,
+        window: 3,
+        advances: 0,
+    };
+    out.put_u32(1);
+    out.put_u32(0xdead_beef);
+    assert_eq!(out.remaining_mut(), 1);
+    assert_eq!(out.advances, 4);
+    assert_eq!(&out.data[..out.len], &[0, 0, 0, 1, 0xde, 0xad, 0xbe, 0xef]);
+
+    let mut frame = Frame {
+        data: out.data[..out.len].to_vec(),
+        pos: 0,
+        window: 2,
+    };
+    assert_eq!(frame.get_u32(), 1);
+    assert_eq!(frame.get_u32(), 0xdead_beef);
+}
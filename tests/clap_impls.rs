@@ -0,0 +1,65 @@
+//! Tests the `clap` definitions, run with `cargo test --features clap`.
+
+#![cfg(feature = "clap")]
+
+use clap::{builder::PossibleValue, Arg, Command, ValueEnum};
+use implem::implem;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Speed {
+    Fast,
+    Slow,
+    /// Not exposed on the command line.
+    Debug,
+}
+implem! {
+    for Speed {
+        ValueEnum {
+            to_possible_value: |&self| match self {
+                Self::Fast => Some(PossibleValue::new("fast")),
+                Self::Slow => Some(PossibleValue::new("slow").alias("s")),
+                Self::Debug => None,
+            },
+            value_variants: || &[Self::Fast, Self::Slow],
+        }
+    }
+}
+
+fn command() -> Command {
+    Command::new("run").arg(
+        Arg::new("speed")
+            .long("speed")
+            .value_parser(clap::value_parser!(Speed)),
+    )
+}
+
+#[test]
+fn parse() {
+    let matches = command().get_matches_from(["run", "--speed", "slow"]);
+    assert_eq!(matches.get_one::<Speed>("speed"), Some(&Speed::Slow));
+    let matches = command().get_matches_from(["run", "--speed=s"]);
+    assert_eq!(matches.get_one::<Speed>("speed"), Some(&Speed::Slow));
+    let matches = command().get_matches_from(["run", "--speed", "fast"]);
+    assert_eq!(matches.get_one::<Speed>("speed"), Some(&Speed::Fast));
+    let matches = command().get_matches_from(["run"]);
+    assert_eq!(matches.get_one::<Speed>("speed"), None);
+
+    let err = command()
+        .try_get_matches_from(["run", "--speed", "debug"])
+        .err()
+        .unwrap();
+    assert!(
+        err.to_string().contains("[possible values: fast, slow]"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn from_str() {
+    assert_eq!(Speed::from_str("fast", false), Ok(Speed::Fast));
+    assert_eq!(Speed::from_str("FAST", true), Ok(Speed::Fast));
+    assert_eq!(Speed::from_str("S", true), Ok(Speed::Slow));
+    assert!(Speed::from_str("FAST", false).is_err());
+    assert_eq!(Speed::Debug.to_possible_value(), None);
+}
@@ -0,0 +1,531 @@
+//! Checks the messages of the errors reported by `implem!`, which `compile_fail` doc tests cannot
+//! look at: snippets are compiled with `rustc` against the library, and their errors compared to
+//! the expected messages.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+/// The `rustc` of the toolchain running the tests.
+fn rustc() -> PathBuf {
+    if let Some(rustc) = std::env::var_os("RUSTC") {
+        return rustc.into();
+    }
+    std::env::var_os("CARGO")
+        .map(|cargo| {
+            Path::new(&cargo).with_file_name(format!("rustc{}", std::env::consts::EXE_SUFFIX))
+        })
+        .filter(|rustc| rustc.exists())
+        .unwrap_or_else(|| "rustc".into())
+}
+
+fn out_dir() -> PathBuf {
+    Path::new(env!("CARGO_TARGET_TMPDIR")).join("compile_errors")
+}
+
+/// Builds the library once, returns the path of the rlib.
+fn implem_rlib() -> &'static Path {
+    static RLIB: OnceLock<PathBuf> = OnceLock::new();
+    RLIB.get_or_init(|| {
+        let out_dir = out_dir();
+        std::fs::create_dir_all(&out_dir).unwrap();
+        let output = Command::new(rustc())
+            .args([
+                "--crate-name",
+                "implem",
+                "--crate-type",
+                "rlib",
+                "--edition",
+                "2018",
+            ])
+            .arg("--out-dir")
+            .arg(&out_dir)
+            .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs"))
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "failed to build `implem`:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        out_dir.join("libimplem.rlib")
+    })
+}
+
+/// Compiles `source` as a library using `implem`, returns the compiler's output.
+///
+/// Panics if the compilation succeeds.
+fn compile_fail(name: &str, source: &str) -> String {
+    let mut child = Command::new(rustc())
+        .args([
+            "--crate-type",
+            "lib",
+            "--edition",
+            "2018",
+            "--emit",
+            "metadata",
+        ])
+        .args(["--crate-name", name])
+        .arg("--extern")
+        .arg(format!("implem={}", implem_rlib().display()))
+        .arg("-o")
+        .arg(out_dir().join(format!("lib{}.rmeta", name)))
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let header = "#![allow(dead_code)]\nuse implem::implem;\n";
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(header.as_bytes()).unwrap();
+    stdin.write_all(source.as_bytes()).unwrap();
+    drop(stdin);
+    let output = child.wait_with_output().unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!output.status.success(), "`{}` compiled:\n{}", name, source);
+    stderr
+}
+
+/// Checks that compiling `source` fails with errors containing all the `expected` fragments.
+///
+/// Types in messages are printed by `stringify!`, whose spacing varies between compilers, so
+/// fragments usually stop before them.
+fn assert_error(name: &str, source: &str, expected: &[&str]) {
+    let stderr = compile_fail(name, source);
+    for expected in expected {
+        assert!(
+            stderr.contains(expected),
+            "expected an error containing\n{}\ngot\n{}",
+            expected,
+            stderr
+        );
+    }
+}
+
+#[test]
+fn self_ty_unclosed() {
+    assert_error(
+        "self_ty_unclosed",
+        r#"
+pub struct MyType<T>(T);
+implem! {
+    impl(T) for MyType<T where (T: std::fmt::Debug) {
+        Debug {
+            |&self, fmt| write!(fmt, "MyType({:?})", self.0)
+        }
+    }
+}
+"#,
+        &[
+            "failed to parse the type after `for`, unclosed `<` in `MyType",
+            "`; check your angle brackets and lifetimes",
+        ],
+    );
+}
+
+#[test]
+fn self_ty_unexpected() {
+    // The header of the second block is broken, the first one is fine.
+    assert_error(
+        "self_ty_unexpected",
+        r#"
+pub struct Fine;
+pub struct MyType<T>(T);
+implem! {
+    for Fine {
+        Debug {
+            |&self, fmt| write!(fmt, "Fine")
+        }
+    }
+    impl(T) for MyType<T>> {
+        Debug {
+            |&self, fmt| write!(fmt, "MyType")
+        }
+    }
+}
+"#,
+        &["failed to parse the type after `for`, unexpected `>>` after `MyType"],
+    );
+}
+
+#[test]
+fn missing_body() {
+    assert_error(
+        "missing_body",
+        r#"
+pub struct MyType;
+implem! {
+    for MyType
+}
+"#,
+        &["expected `{ ... }` definitions after the type `MyType`"],
+    );
+}
+
+#[test]
+fn unknown_trait() {
+    assert_error(
+        "unknown_trait",
+        r#"
+pub struct MyStruct;
+implem! {
+    for MyStruct {
+        Diplay {
+            |&self, fmt| write!(fmt, "MyStruct")
+        }
+    }
+}
+"#,
+        &[
+            "expected known trait, got `Diplay`",
+            "supported traits are:",
+            "- Display, Debug, Binary, Octal, LowerHex, UpperHex, Pointer, LowerExp, UpperExp",
+            "- any other trait as `impl MyTrait { ... }`",
+        ],
+    );
+}
+
+#[test]
+fn unknown_iterator_method() {
+    assert_error(
+        "unknown_iterator_method",
+        r#"
+pub struct Countdown {
+    left: usize,
+}
+implem! {
+    for Countdown {
+        Iterator<Item = usize> {
+            next: |&mut self| None,
+            last: |self| None,
+        }
+    }
+}
+"#,
+        &[
+            "unknown or malformed `Iterator` method `last`, expected one of",
+            "- next: |&mut self| ...",
+            "- fold: |self, init, f| ...",
+        ],
+    );
+    // Known label, wrong receiver.
+    assert_error(
+        "malformed_iterator_method",
+        r#"
+pub struct Countdown {
+    left: usize,
+}
+implem! {
+    for Countdown {
+        Iterator<Item = usize> {
+            next: |&self| None,
+        }
+    }
+}
+"#,
+        &["unknown or malformed `Iterator` method `next`, expected one of"],
+    );
+}
+
+#[test]
+fn by_value_fmt_receiver() {
+    assert_error(
+        "by_value_display_receiver",
+        r#"
+#[derive(Clone, Copy)]
+pub struct Celsius(f64);
+implem! {
+    for Celsius {
+        Display {
+            |self, fmt| write!(fmt, "{}°C", self.0)
+        }
+    }
+}
+"#,
+        &["`Display` definitions take `|&self, ..|`: the method they define takes `&self`"],
+    );
+    assert_error(
+        "by_value_debug_receiver",
+        r#"
+pub struct Celsius(f64);
+implem! {
+    for Celsius {
+        Debug {
+            |self, fmt| write!(fmt, "Celsius({})", self.0)
+        }
+    }
+}
+"#,
+        &["`Debug` definitions take `|&self, ..|`"],
+    );
+    // Another name than `self`.
+    assert_error(
+        "non_self_display_receiver",
+        r#"
+pub struct Celsius(f64);
+implem! {
+    for Celsius {
+        Display {
+            |s, fmt| write!(fmt, "{}°C", s.0)
+        }
+    }
+}
+"#,
+        &["`Display` definitions take `|&self, ..|`: the first parameter must be `&self`, got `s`"],
+    );
+    // Traits that are not formatting traits are still reported as unknown.
+    assert_error(
+        "by_value_unknown_receiver",
+        r#"
+pub struct Celsius(f64);
+implem! {
+    for Celsius {
+        Diplay {
+            |self, fmt| write!(fmt, "{}°C", self.0)
+        }
+    }
+}
+"#,
+        &["expected known trait, got `Diplay`"],
+    );
+}
+
+#[test]
+fn const_impl_without_nightly() {
+    assert_error(
+        "const_impl_without_nightly",
+        r#"
+pub struct Celsius(f64);
+implem! {
+    for Celsius {
+        const From<f64> {
+            |degrees| Self(degrees)
+        }
+    }
+}
+"#,
+        &["`const From` requires the `nightly` feature of `implem`"],
+    );
+}
+
+#[test]
+fn generic_newtype() {
+    assert_error(
+        "generic_newtype",
+        r#"
+use implem::impl_newtype;
+pub struct Wrapper<T>(T);
+impl_newtype!(Wrapper<T>, T);
+"#,
+        &["`impl_newtype!` does not support generic newtypes such as `Wrapper<...>`"],
+    );
+}
+
+#[test]
+fn comparable_and_hashable() {
+    // Both bundles implement `PartialEq` and `Eq`, `Comparable + Hashable` is the way to go.
+    assert_error(
+        "comparable_and_hashable",
+        r#"
+pub struct User {
+    id: u64,
+}
+implem! {
+    for User {
+        Comparable<Key = u64> {
+            |&self| self.id
+        }
+        Hashable<Key = u64> {
+            |&self| self.id
+        }
+    }
+}
+"#,
+        &[
+            "error[E0119]: conflicting implementations of trait `PartialEq` for type `User`",
+            "error[E0119]: conflicting implementations of trait `Eq` for type `User`",
+        ],
+    );
+}
+
+#[test]
+fn index_mut_without_index() {
+    // No dedicated message, the compiler already names the missing impl and the bound requiring it.
+    assert_error(
+        "index_mut_without_index",
+        r#"
+pub struct Buffer(Vec<u8>);
+implem! {
+    for Buffer {
+        IndexMut<usize> {
+            |&mut self, idx| &mut self.0[idx]
+        }
+    }
+}
+"#,
+        &[
+            "error[E0277]: the type `Buffer` cannot be indexed by `usize`",
+            "required by a bound in `IndexMut`",
+        ],
+    );
+}
+
+/// Definitions of traits that are only available with a feature, along with the feature and the
+/// trait reported.
+const GATED: &[(&str, &str, &str)] = &[
+    (
+        "nightly",
+        "FnOnce",
+        "FnOnce<(u32,), Output = u32> { |self, (n,)| self.0 + n }",
+    ),
+    (
+        "nightly",
+        "FnMut",
+        "FnMut<(u32,)> { |&mut self, (n,)| self.0 + n }",
+    ),
+    ("nightly", "Fn", "Fn<(u32,)> { |&self, (n,)| self.0 + n }"),
+    (
+        "nightly",
+        "Try",
+        "Try<Output = u32, Residual = Option<Infallible>> { |n| Self(n), |self| Continue(self.0) }",
+    ),
+    (
+        "nightly",
+        "FromResidual",
+        "FromResidual<Option<Infallible>> { |_| Self(0) }",
+    ),
+    (
+        "nightly",
+        "Step",
+        "Step { forward_checked: |start, count| Some(Self(start.0 + count as u32)) }",
+    ),
+    (
+        "serde",
+        "Serialize",
+        "Serialize { |&self, serializer| serializer.serialize_u32(self.0) }",
+    ),
+    (
+        "serde",
+        "Deserialize",
+        "Deserialize { |deserializer| u32::deserialize(deserializer).map(Self) }",
+    ),
+    ("serde", "SerializeViaDisplay", "SerializeViaDisplay {}"),
+    ("serde", "DeserializeViaFromStr", "DeserializeViaFromStr {}"),
+    ("serde", "SerdeViaStr", "SerdeViaStr {}"),
+    (
+        "futures",
+        "Stream",
+        "Stream<Item = u32> { |self: Pin<&mut Self>, cx| Poll::Ready(None) }",
+    ),
+    (
+        "futures",
+        "Sink",
+        "Sink<u32, Error = Infallible> { start_send: |self: Pin<&mut Self>, item| Ok(()) }",
+    ),
+    (
+        "tokio-io",
+        "AsyncRead",
+        "AsyncRead { |self: Pin<&mut Self>, cx, buf| Poll::Ready(Ok(())) }",
+    ),
+    (
+        "tokio-io",
+        "AsyncWrite",
+        "AsyncWrite { poll_write: |self: Pin<&mut Self>, cx, buf| Poll::Ready(Ok(buf.len())) }",
+    ),
+    (
+        "rayon",
+        "IntoParallelIterator",
+        "IntoParallelIterator<Item = u32, Iter = Once<u32>> { |self| once(self.0) }",
+    ),
+    (
+        "num-traits",
+        "Zero",
+        "Zero { || Self(0), |&self| self.0 == 0 }",
+    ),
+    ("num-traits", "One", "One { || Self(1) }"),
+    (
+        "num-traits",
+        "FromPrimitive",
+        "FromPrimitive { from_u64: |n| None }",
+    ),
+    (
+        "num-traits",
+        "ToPrimitive",
+        "ToPrimitive { to_u64: |&self| Some(self.0.into()) }",
+    ),
+    ("quickcheck", "Arbitrary", "Arbitrary { |g| Self(0) }"),
+    (
+        "proptest",
+        "ProptestArbitrary",
+        "ProptestArbitrary<Strategy = Box<Self>> { |_| Box::new(Self(0)) }",
+    ),
+    (
+        "arbitrary",
+        "Arbitrary",
+        "Arbitrary<'a> { |u| Ok(Self(0)) }",
+    ),
+    ("rand", "RandStandard", "RandStandard { |rng| Gated(0) }"),
+    ("bytes", "Buf", "Buf { remaining: |&self| 0 }"),
+    (
+        "bytes",
+        "BufMut",
+        "unsafe BufMut { remaining_mut: |&self| 0 }",
+    ),
+    ("clap", "ValueEnum", "ValueEnum { value_variants: || &[] }"),
+    (
+        "approx",
+        "AbsDiffEq",
+        "AbsDiffEq<Epsilon = u32> { default_epsilon: || 0 }",
+    ),
+    (
+        "approx",
+        "RelativeEq",
+        "RelativeEq { default_max_relative: || 0 }",
+    ),
+    (
+        "equivalent",
+        "Equivalent",
+        "Equivalent<u32> { |&self, key| self.0 == *key }",
+    ),
+    (
+        "borsh",
+        "BorshSerialize",
+        "BorshSerialize { |&self, writer| writer.write_all(&self.0.to_le_bytes()) }",
+    ),
+    (
+        "borsh",
+        "BorshDeserialize",
+        "BorshDeserialize { |reader| Ok(Self(0)) }",
+    ),
+    (
+        "ufmt",
+        "uDisplay",
+        "uDisplay { |&self, fmt| fmt.write_str(\"Gated\") }",
+    ),
+    (
+        "ufmt",
+        "uDebug",
+        "uDebug { |&self, fmt| fmt.write_str(\"Gated\") }",
+    ),
+    ("defmt", "Format", "Format { |&self, f| () }"),
+    ("zeroize", "Zeroize", "Zeroize { |&mut self| self.0 = 0 }"),
+    ("zeroize", "ZeroizeOnDrop", "ZeroizeOnDrop {}"),
+];
+
+#[test]
+fn feature_required() {
+    // The library is built without features.
+    for (i, (feature, trait_name, definition)) in GATED.iter().enumerate() {
+        let source = format!(
+            "pub struct Gated(u32);\nimplem! {{\n    for Gated {{\n        {}\n    }}\n}}\n",
+            definition
+        );
+        let expected = format!(
+            "trait `{}` requires the `{}` feature of `implem`",
+            trait_name, feature
+        );
+        assert_error(&format!("feature_required_{}", i), &source, &[&expected]);
+    }
+}
@@ -0,0 +1,40 @@
+//! Tests the `defmt` definitions, run with `cargo test --features defmt`.
+//!
+//! Logging needs a target and a global logger, so these only check that the expansions compile.
+
+#![cfg(feature = "defmt")]
+
+use implem::implem;
+
+pub struct Temp {
+    celsius: f32,
+}
+implem! {
+    for Temp {
+        Format {
+            |&self, f| defmt::write!(f, "Temp({=f32})", self.celsius)
+        }
+    }
+}
+
+pub struct Reading<'a, T> {
+    sensor: &'a str,
+    value: T,
+}
+implem! {
+    impl('a, T) for Reading<'a, T> where (T: defmt::Format) {
+        Format {
+            |&self, f| defmt::write!(f, "{=str}: {}", self.sensor, self.value)
+        }
+    }
+}
+
+fn assert_format<T: defmt::Format + ?Sized>() {}
+
+#[test]
+fn format() {
+    assert_format::<Temp>();
+    assert_format::<Reading<'static, Temp>>();
+    assert_format::<Reading<'static, u32>>();
+    assert_format::<&Reading<'static, Reading<'static, Temp>>>();
+}
@@ -0,0 +1,44 @@
+//! Tests the `equivalent` definitions, run with `cargo test --features equivalent`.
+
+#![cfg(feature = "equivalent")]
+
+use equivalent::Equivalent;
+use implem::implem;
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct UserName(String);
+
+/// Case-insensitive lookup.
+pub struct Lookup<'a>(&'a str);
+implem! {
+    impl('a) for Lookup<'a> {
+        Equivalent<UserName> {
+            |&self, key| self.0.eq_ignore_ascii_case(&key.0)
+        }
+    }
+}
+
+/// Map looking its keys up with `Equivalent`, like `indexmap` and `hashbrown`.
+pub struct VecMap<K, V>(Vec<(K, V)>);
+impl<K, V> VecMap<K, V> {
+    pub fn get<Q: ?Sized + Equivalent<K>>(&self, query: &Q) -> Option<&V> {
+        self.0
+            .iter()
+            .find(|(key, _)| query.equivalent(key))
+            .map(|(_, value)| value)
+    }
+}
+
+#[test]
+fn lookup() {
+    let ages = VecMap(vec![
+        (UserName("alice".into()), 31),
+        (UserName("bob".into()), 42),
+    ]);
+    assert_eq!(ages.get(&Lookup("Alice")), Some(&31));
+    assert_eq!(ages.get(&Lookup("BOB")), Some(&42));
+    assert_eq!(ages.get(&Lookup("carol")), None);
+    // The blanket impl still applies to the key type itself.
+    assert_eq!(ages.get(&UserName("bob".into())), Some(&42));
+    assert_eq!(ages.get(&UserName("Bob".into())), None);
+}
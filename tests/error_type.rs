@@ -0,0 +1,226 @@
+//! Tests `impl_error_type!` on enums the doc examples are too small for.
+
+use implem::impl_error_type;
+
+/// Enough variants to hit the recursion limit if variants were not handled in one step.
+#[derive(Debug)]
+pub enum ManyErrors {
+    Unit0,
+    Code1(u32),
+    Span2 { start: usize, end: usize },
+    Triple3(u8, u8, u8),
+    Unit4,
+    Code5(u32),
+    Span6 { start: usize, end: usize },
+    Triple7(u8, u8, u8),
+    Unit8,
+    Code9(u32),
+    Span10 { start: usize, end: usize },
+    Triple11(u8, u8, u8),
+    Unit12,
+    Code13(u32),
+    Span14 { start: usize, end: usize },
+    Triple15(u8, u8, u8),
+    Unit16,
+    Code17(u32),
+    Span18 { start: usize, end: usize },
+    Triple19(u8, u8, u8),
+    Unit20,
+    Code21(u32),
+    Span22 { start: usize, end: usize },
+    Triple23(u8, u8, u8),
+    Unit24,
+    Code25(u32),
+    Span26 { start: usize, end: usize },
+    Triple27(u8, u8, u8),
+    Unit28,
+    Code29(u32),
+    Span30 { start: usize, end: usize },
+    Triple31(u8, u8, u8),
+    Unit32,
+    Code33(u32),
+    Span34 { start: usize, end: usize },
+    Triple35(u8, u8, u8),
+    Unit36,
+    Code37(u32),
+    Span38 { start: usize, end: usize },
+    Triple39(u8, u8, u8),
+    Unit40,
+    Code41(u32),
+    Span42 { start: usize, end: usize },
+    Triple43(u8, u8, u8),
+    Unit44,
+    Code45(u32),
+    Span46 { start: usize, end: usize },
+    Triple47(u8, u8, u8),
+}
+impl_error_type!(ManyErrors;
+    Unit0 => "unit 0",
+    Code1(code) => "code 1: {}", code,
+    Span2 { start, end } => "span 2: {}..{}", start, end,
+    Triple3(a, b, c) => "triple 3: {} {} {}", a, b, c,
+    Unit4 => "unit 4",
+    Code5(code) => "code 5: {}", code,
+    Span6 { start, end } => "span 6: {}..{}", start, end,
+    Triple7(a, b, c) => "triple 7: {} {} {}", a, b, c,
+    Unit8 => "unit 8",
+    Code9(code) => "code 9: {}", code,
+    Span10 { start, end } => "span 10: {}..{}", start, end,
+    Triple11(a, b, c) => "triple 11: {} {} {}", a, b, c,
+    Unit12 => "unit 12",
+    Code13(code) => "code 13: {}", code,
+    Span14 { start, end } => "span 14: {}..{}", start, end,
+    Triple15(a, b, c) => "triple 15: {} {} {}", a, b, c,
+    Unit16 => "unit 16",
+    Code17(code) => "code 17: {}", code,
+    Span18 { start, end } => "span 18: {}..{}", start, end,
+    Triple19(a, b, c) => "triple 19: {} {} {}", a, b, c,
+    Unit20 => "unit 20",
+    Code21(code) => "code 21: {}", code,
+    Span22 { start, end } => "span 22: {}..{}", start, end,
+    Triple23(a, b, c) => "triple 23: {} {} {}", a, b, c,
+    Unit24 => "unit 24",
+    Code25(code) => "code 25: {}", code,
+    Span26 { start, end } => "span 26: {}..{}", start, end,
+    Triple27(a, b, c) => "triple 27: {} {} {}", a, b, c,
+    Unit28 => "unit 28",
+    Code29(code) => "code 29: {}", code,
+    Span30 { start, end } => "span 30: {}..{}", start, end,
+    Triple31(a, b, c) => "triple 31: {} {} {}", a, b, c,
+    Unit32 => "unit 32",
+    Code33(code) => "code 33: {}", code,
+    Span34 { start, end } => "span 34: {}..{}", start, end,
+    Triple35(a, b, c) => "triple 35: {} {} {}", a, b, c,
+    Unit36 => "unit 36",
+    Code37(code) => "code 37: {}", code,
+    Span38 { start, end } => "span 38: {}..{}", start, end,
+    Triple39(a, b, c) => "triple 39: {} {} {}", a, b, c,
+    Unit40 => "unit 40",
+    Code41(code) => "code 41: {}", code,
+    Span42 { start, end } => "span 42: {}..{}", start, end,
+    Triple43(a, b, c) => "triple 43: {} {} {}", a, b, c,
+    Unit44 => "unit 44",
+    Code45(code) => "code 45: {}", code,
+    Span46 { start, end } => "span 46: {}..{}", start, end,
+    Triple47(a, b, c) => "triple 47: {} {} {}", a, b, c,
+);
+
+#[test]
+fn many_variants() {
+    assert_eq!(ManyErrors::Unit0.to_string(), "unit 0");
+    assert_eq!(ManyErrors::Code1(7).to_string(), "code 1: 7");
+    assert_eq!(
+        ManyErrors::Span2 { start: 1, end: 2 }.to_string(),
+        "span 2: 1..2"
+    );
+    assert_eq!(ManyErrors::Triple3(1, 2, 3).to_string(), "triple 3: 1 2 3");
+    assert_eq!(ManyErrors::Unit4.to_string(), "unit 4");
+    assert_eq!(ManyErrors::Code5(7).to_string(), "code 5: 7");
+    assert_eq!(
+        ManyErrors::Span6 { start: 1, end: 2 }.to_string(),
+        "span 6: 1..2"
+    );
+    assert_eq!(ManyErrors::Triple7(1, 2, 3).to_string(), "triple 7: 1 2 3");
+    assert_eq!(ManyErrors::Unit8.to_string(), "unit 8");
+    assert_eq!(ManyErrors::Code9(7).to_string(), "code 9: 7");
+    assert_eq!(
+        ManyErrors::Span10 { start: 1, end: 2 }.to_string(),
+        "span 10: 1..2"
+    );
+    assert_eq!(
+        ManyErrors::Triple11(1, 2, 3).to_string(),
+        "triple 11: 1 2 3"
+    );
+    assert_eq!(ManyErrors::Unit12.to_string(), "unit 12");
+    assert_eq!(ManyErrors::Code13(7).to_string(), "code 13: 7");
+    assert_eq!(
+        ManyErrors::Span14 { start: 1, end: 2 }.to_string(),
+        "span 14: 1..2"
+    );
+    assert_eq!(
+        ManyErrors::Triple15(1, 2, 3).to_string(),
+        "triple 15: 1 2 3"
+    );
+    assert_eq!(ManyErrors::Unit16.to_string(), "unit 16");
+    assert_eq!(ManyErrors::Code17(7).to_string(), "code 17: 7");
+    assert_eq!(
+        ManyErrors::Span18 { start: 1, end: 2 }.to_string(),
+        "span 18: 1..2"
+    );
+    assert_eq!(
+        ManyErrors::Triple19(1, 2, 3).to_string(),
+        "triple 19: 1 2 3"
+    );
+    assert_eq!(ManyErrors::Unit20.to_string(), "unit 20");
+    assert_eq!(ManyErrors::Code21(7).to_string(), "code 21: 7");
+    assert_eq!(
+        ManyErrors::Span22 { start: 1, end: 2 }.to_string(),
+        "span 22: 1..2"
+    );
+    assert_eq!(
+        ManyErrors::Triple23(1, 2, 3).to_string(),
+        "triple 23: 1 2 3"
+    );
+    assert_eq!(ManyErrors::Unit24.to_string(), "unit 24");
+    assert_eq!(ManyErrors::Code25(7).to_string(), "code 25: 7");
+    assert_eq!(
+        ManyErrors::Span26 { start: 1, end: 2 }.to_string(),
+        "span 26: 1..2"
+    );
+    assert_eq!(
+        ManyErrors::Triple27(1, 2, 3).to_string(),
+        "triple 27: 1 2 3"
+    );
+    assert_eq!(ManyErrors::Unit28.to_string(), "unit 28");
+    assert_eq!(ManyErrors::Code29(7).to_string(), "code 29: 7");
+    assert_eq!(
+        ManyErrors::Span30 { start: 1, end: 2 }.to_string(),
+        "span 30: 1..2"
+    );
+    assert_eq!(
+        ManyErrors::Triple31(1, 2, 3).to_string(),
+        "triple 31: 1 2 3"
+    );
+    assert_eq!(ManyErrors::Unit32.to_string(), "unit 32");
+    assert_eq!(ManyErrors::Code33(7).to_string(), "code 33: 7");
+    assert_eq!(
+        ManyErrors::Span34 { start: 1, end: 2 }.to_string(),
+        "span 34: 1..2"
+    );
+    assert_eq!(
+        ManyErrors::Triple35(1, 2, 3).to_string(),
+        "triple 35: 1 2 3"
+    );
+    assert_eq!(ManyErrors::Unit36.to_string(), "unit 36");
+    assert_eq!(ManyErrors::Code37(7).to_string(), "code 37: 7");
+    assert_eq!(
+        ManyErrors::Span38 { start: 1, end: 2 }.to_string(),
+        "span 38: 1..2"
+    );
+    assert_eq!(
+        ManyErrors::Triple39(1, 2, 3).to_string(),
+        "triple 39: 1 2 3"
+    );
+    assert_eq!(ManyErrors::Unit40.to_string(), "unit 40");
+    assert_eq!(ManyErrors::Code41(7).to_string(), "code 41: 7");
+    assert_eq!(
+        ManyErrors::Span42 { start: 1, end: 2 }.to_string(),
+        "span 42: 1..2"
+    );
+    assert_eq!(
+        ManyErrors::Triple43(1, 2, 3).to_string(),
+        "triple 43: 1 2 3"
+    );
+    assert_eq!(ManyErrors::Unit44.to_string(), "unit 44");
+    assert_eq!(ManyErrors::Code45(7).to_string(), "code 45: 7");
+    assert_eq!(
+        ManyErrors::Span46 { start: 1, end: 2 }.to_string(),
+        "span 46: 1..2"
+    );
+    assert_eq!(
+        ManyErrors::Triple47(1, 2, 3).to_string(),
+        "triple 47: 1 2 3"
+    );
+    let boxed: Box<dyn std::error::Error> = Box::new(ManyErrors::Unit0);
+    assert_eq!(boxed.to_string(), "unit 0");
+}
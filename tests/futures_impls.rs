@@ -0,0 +1,118 @@
+//! Tests the `futures` definitions, run with `cargo test --features futures`.
+
+#![cfg(feature = "futures")]
+
+use std::task::Poll;
+
+use futures::executor::{block_on, block_on_stream};
+use futures::SinkExt;
+use implem::implem;
+
+/// Counts down, pending every other poll.
+pub struct Countdown {
+    left: u32,
+    ready: bool,
+}
+implem! {
+    for Countdown {
+        Stream<Item = u32> {
+            |self: Pin<&mut Self>, cx| {
+                let this = self.get_mut();
+                this.ready = !this.ready;
+                if !this.ready {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                } else if this.left == 0 {
+                    Poll::Ready(None)
+                } else {
+                    this.left -= 1;
+                    Poll::Ready(Some(this.left))
+                }
+            },
+            |&self| (self.left as usize, Some(self.left as usize)),
+        }
+    }
+}
+
+/// Repeats a value, without a `size_hint`.
+pub struct Repeat<T>(T);
+implem! {
+    impl(T) for Repeat<T> where (T: Clone + Unpin) {
+        Stream<Item = T> {
+            |self: Pin<&mut Self>, _cx| Poll::Ready(Some(self.0.clone()))
+        }
+    }
+}
+
+#[test]
+fn stream() {
+    let mut stream = block_on_stream(Countdown {
+        left: 3,
+        ready: false,
+    });
+    assert_eq!(stream.size_hint(), (3, Some(3)));
+    assert_eq!(stream.next(), Some(2));
+    assert_eq!(stream.size_hint(), (2, Some(2)));
+    assert_eq!(stream.by_ref().collect::<Vec<_>>(), vec![1, 0]);
+    assert_eq!(stream.next(), None);
+
+    let repeat = block_on_stream(Repeat("a"));
+    assert_eq!(repeat.size_hint(), (0, None));
+    assert_eq!(repeat.take(2).collect::<Vec<_>>(), vec!["a", "a"]);
+}
+
+/// Writes batches of lines on flush.
+#[derive(Default)]
+pub struct Batcher {
+    batch: Vec<String>,
+    written: Vec<Vec<String>>,
+    closed: bool,
+}
+implem! {
+    for Batcher {
+        Sink<String, Error = String> {
+            poll_ready: |self: Pin<&mut Self>, _cx| if self.closed {
+                Poll::Ready(Err("closed".into()))
+            } else {
+                Poll::Ready(Ok(()))
+            },
+            start_send: |self: Pin<&mut Self>, line| {
+                self.get_mut().batch.push(line);
+                Ok(())
+            },
+            poll_flush: |self: Pin<&mut Self>, _cx| {
+                let this = self.get_mut();
+                if !this.batch.is_empty() {
+                    this.written.push(std::mem::take(&mut this.batch));
+                }
+                Poll::Ready(Ok(()))
+            },
+            poll_close: |self: Pin<&mut Self>, cx| {
+                let this = self.get_mut();
+                this.closed = true;
+                std::pin::Pin::new(this).poll_flush(cx)
+            },
+        }
+    }
+}
+
+#[test]
+fn sink() {
+    let mut sink = Batcher::default();
+    block_on(async {
+        sink.send("a".to_string()).await?;
+        sink.send("b".to_string()).await?;
+        sink.close().await
+    })
+    .unwrap();
+    assert!(sink.batch.is_empty());
+    assert_eq!(
+        sink.written,
+        vec![vec!["a".to_string()], vec!["b".to_string()]]
+    );
+    assert!(sink.closed);
+    assert_eq!(
+        block_on(sink.send("c".to_string())),
+        Err("closed".to_string())
+    );
+}
@@ -0,0 +1,174 @@
+//! Tests the `nightly` definitions, run with `cargo +nightly test --features nightly`.
+
+#![cfg(feature = "nightly")]
+#![cfg_attr(
+    feature = "nightly",
+    feature(
+        fn_traits,
+        unboxed_closures,
+        try_trait_v2,
+        try_trait_v2_residual,
+        step_trait
+    )
+)]
+
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::iter::Step;
+use std::ops::{ControlFlow, Try};
+
+use implem::implem;
+
+pub struct Adder {
+    offset: i32,
+}
+implem! {
+    for Adder {
+        FnOnce<(i32, i32), Output = i32> {
+            |self, (a, b)| self.offset + a + b
+        }
+        FnMut<(i32, i32)> {
+            |&mut self, (a, b)| self.offset + a + b
+        }
+        Fn<(i32, i32)> {
+            |&self, (a, b)| self.offset + a + b
+        }
+    }
+}
+
+pub struct Counter {
+    count: usize,
+}
+implem! {
+    for Counter {
+        FnOnce<(), Output = usize> {
+            |self, ()| self.count + 1
+        }
+        FnMut<()> {
+            |&mut self, ()| {
+                self.count += 1;
+                self.count
+            }
+        }
+    }
+}
+
+fn apply(f: impl Fn(i32, i32) -> i32, a: i32, b: i32) -> i32 {
+    f(a, b)
+}
+
+#[test]
+fn fn_traits() {
+    let adder = Adder { offset: 10 };
+    assert_eq!(adder(1, 2), 13);
+    assert_eq!(Fn::call(&adder, (0, 0)), 10);
+    assert_eq!(apply(&adder, 5, -5), 10);
+    let sums: Vec<i32> = [(1, 1), (2, 3)].iter().map(|&(a, b)| adder(a, b)).collect();
+    assert_eq!(sums, vec![12, 15]);
+    assert_eq!(FnOnce::call_once(adder, (1, 1)), 12);
+
+    let mut counter = Counter { count: 0 };
+    assert_eq!(counter(), 1);
+    assert_eq!(FnMut::call_mut(&mut counter, ()), 2);
+    assert_eq!(counter.count, 2);
+    // `FnOnce` does not go through `FnMut`.
+    assert_eq!(FnOnce::call_once(counter, ()), 3);
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fallible<T> {
+    Value(T),
+    Failure(String),
+}
+implem! {
+    impl(T) for Fallible<T> {
+        Try<Output = T, Residual = Fallible<Infallible>> {
+            |value| Fallible::Value(value),
+            |self| match self {
+                Fallible::Value(value) => ControlFlow::Continue(value),
+                Fallible::Failure(msg) => ControlFlow::Break(Fallible::Failure(msg)),
+            },
+        }
+        FromResidual {
+            |residual| match residual {
+                Fallible::Value(never) => match never {},
+                Fallible::Failure(msg) => Fallible::Failure(msg),
+            }
+        }
+    }
+    // recent nightlies require the residual to know the `Try` type it comes from
+    impl(T) for Fallible<Infallible> {
+        impl std::ops::Residual<T> {
+            type TryType = Fallible<T>;
+        }
+    }
+}
+
+fn parse(s: &str, parsed: &Cell<usize>) -> Fallible<i32> {
+    parsed.set(parsed.get() + 1);
+    match s.parse() {
+        Ok(n) => Fallible::Value(n),
+        Err(e) => Fallible::Failure(format!("`{}`: {}", s, e)),
+    }
+}
+
+fn sum(lft: &str, rgt: &str, parsed: &Cell<usize>) -> Fallible<i32> {
+    Fallible::Value(parse(lft, parsed)? + parse(rgt, parsed)?)
+}
+
+#[test]
+fn try_trait() {
+    assert_eq!(Fallible::from_output(7), Fallible::Value(7));
+    assert_eq!(Fallible::Value(7).branch(), ControlFlow::Continue(7));
+
+    let parsed = Cell::new(0);
+    assert_eq!(sum("1", "2", &parsed), Fallible::Value(3));
+    assert_eq!(parsed.get(), 2);
+
+    let parsed = Cell::new(0);
+    assert_eq!(
+        sum("1", "two", &parsed),
+        Fallible::Failure("`two`: invalid digit found in string".into()),
+    );
+    assert_eq!(parsed.get(), 2);
+
+    // `?` returns early, `rgt` is not parsed.
+    let parsed = Cell::new(0);
+    assert_eq!(
+        sum("one", "2", &parsed),
+        Fallible::Failure("`one`: invalid digit found in string".into()),
+    );
+    assert_eq!(parsed.get(), 1);
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct UserId(u64);
+implem! {
+    for UserId {
+        Step {
+            steps_between: |start, end| u64::steps_between(&start.0, &end.0),
+            forward_checked: |start, count| u64::forward_checked(start.0, count).map(UserId),
+            backward_checked: |start, count| u64::backward_checked(start.0, count).map(UserId),
+        }
+    }
+}
+
+#[test]
+fn step_trait() {
+    let ids = (UserId(0)..UserId(5)).collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![UserId(0), UserId(1), UserId(2), UserId(3), UserId(4)]
+    );
+    let back = (UserId(2)..=UserId(4)).rev().collect::<Vec<_>>();
+    assert_eq!(back, vec![UserId(4), UserId(3), UserId(2)]);
+    let even = (UserId(0)..UserId(7)).step_by(2).collect::<Vec<_>>();
+    assert_eq!(even, vec![UserId(0), UserId(2), UserId(4), UserId(6)]);
+    assert_eq!((UserId(3)..UserId(1)).count(), 0);
+    // `size_hint` relies on `steps_between`.
+    assert_eq!((UserId(3)..UserId(10)).size_hint(), (7, Some(7)));
+    assert_eq!((UserId(3)..UserId(10)).nth(4), Some(UserId(7)));
+
+    assert_eq!(Step::forward_checked(UserId(u64::MAX), 1), None);
+    assert_eq!(Step::backward_checked(UserId(0), 1), None);
+}
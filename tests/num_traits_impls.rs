@@ -0,0 +1,124 @@
+//! Tests the `num-traits` definitions, run with `cargo test --features num-traits`.
+
+#![cfg(feature = "num-traits")]
+
+use std::convert::TryFrom;
+use std::ops::{Add, Mul};
+
+use implem::implem;
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Meters(u32);
+implem! {
+    for Meters {
+        Add<Output = Self> {
+            |self, rhs| Self(self.0 + rhs.0)
+        }
+        Mul<Output = Self> {
+            |self, rhs| Self(self.0 * rhs.0)
+        }
+        Zero {
+            || Self(0),
+            |&self| self.0 == 0
+        }
+        One {
+            || Self(1)
+        }
+    }
+}
+
+fn sum_all<T: Zero + Add<Output = T>>(items: impl IntoIterator<Item = T>) -> T {
+    items.into_iter().fold(T::zero(), |acc, n| acc + n)
+}
+
+fn product_all<T: One + Mul<Output = T>>(items: impl IntoIterator<Item = T>) -> T {
+    items.into_iter().fold(T::one(), |acc, n| acc * n)
+}
+
+#[test]
+fn zero_one() {
+    assert_eq!(sum_all(vec![Meters(1), Meters(2), Meters(3)]), Meters(6));
+    assert!(sum_all(Vec::<Meters>::new()).is_zero());
+    assert!(!Meters(1).is_zero());
+    assert_eq!(product_all(vec![Meters(2), Meters(3)]), Meters(6));
+    assert!(Meters::one().is_one());
+    let mut length = Meters(5);
+    length.set_zero();
+    assert_eq!(length, Meters(0));
+    // works the same as for primitive types
+    assert_eq!(sum_all(vec![1u32, 2, 3]), 6);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Level {
+    Low = 0,
+    High = 1,
+}
+implem! {
+    for Level {
+        FromPrimitive {
+            from_i64: |n| u64::try_from(n).ok().and_then(Self::from_u64),
+            from_u64: |n| match n {
+                0 => Some(Self::Low),
+                1 => Some(Self::High),
+                _ => None,
+            },
+        }
+        ToPrimitive {
+            to_i64: |&self| Some(*self as i64),
+            to_u64: |&self| Some(*self as u64),
+        }
+    }
+}
+
+/// Between 0 and 100.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Percent(u8);
+implem! {
+    for Percent {
+        FromPrimitive {
+            from_u64: |n| if n <= 100 { Some(Self(n as u8)) } else { None },
+            from_i64: |n| u64::try_from(n).ok().and_then(Self::from_u64),
+            from_f64: |n| if (0.0..=100.0).contains(&n) { Some(Self(n.round() as u8)) } else { None },
+        }
+        ToPrimitive {
+            to_u64: |&self| Some(self.0.into()),
+            to_i64: |&self| Some(self.0.into()),
+            to_f64: |&self| Some(f64::from(self.0) / 100.0),
+        }
+    }
+}
+
+#[test]
+fn from_primitive() {
+    assert_eq!(Level::from_u64(0), Some(Level::Low));
+    assert_eq!(Level::from_u64(1), Some(Level::High));
+    assert_eq!(Level::from_u64(2), None);
+    assert_eq!(Level::from_i64(-1), None);
+    assert_eq!(Level::from_i64(i64::MIN), None);
+    assert_eq!(Level::from_u64(u64::MAX), None);
+    // defaults going through the required methods
+    assert_eq!(Level::from_u8(1), Some(Level::High));
+    assert_eq!(Level::from_i32(-3), None);
+    assert_eq!(Level::from_f64(1.0), Some(Level::High));
+
+    assert_eq!(Percent::from_u64(0), Some(Percent(0)));
+    assert_eq!(Percent::from_u64(100), Some(Percent(100)));
+    assert_eq!(Percent::from_u64(101), None);
+    assert_eq!(Percent::from_i64(-1), None);
+    assert_eq!(Percent::from_f64(99.6), Some(Percent(100)));
+    assert_eq!(Percent::from_f64(100.5), None);
+}
+
+#[test]
+fn to_primitive() {
+    assert_eq!(Level::Low.to_i64(), Some(0));
+    assert_eq!(Level::High.to_u64(), Some(1));
+    assert_eq!(Level::High.to_i8(), Some(1));
+    assert_eq!(Level::High.to_f64(), Some(1.0));
+
+    assert_eq!(Percent(100).to_u8(), Some(100));
+    assert_eq!(Percent(100).to_i8(), Some(100));
+    assert_eq!(Percent(50).to_f64(), Some(0.5));
+}
@@ -0,0 +1,47 @@
+//! Tests the `proptest` definitions, run with `cargo test --features proptest`.
+
+#![cfg(feature = "proptest")]
+
+use implem::implem;
+use proptest::prelude::*;
+
+#[derive(Debug)]
+pub struct Even(u64);
+implem! {
+    for Even {
+        ProptestArbitrary<Strategy = BoxedStrategy<Self>> {
+            |_| any::<u64>().prop_map(|n| Self(n & !1)).boxed()
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Below(u64);
+implem! {
+    for Below {
+        ProptestArbitrary<Parameters = u64, Strategy = BoxedStrategy<Self>> {
+            |max| (0..max.max(1)).prop_map(Self).boxed()
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn even_is_even(n: Even) {
+        prop_assert_eq!(n.0 % 2, 0);
+    }
+
+    #[test]
+    fn below_is_below(n in Below::arbitrary_with(10), m: Below) {
+        prop_assert!(n.0 < 10);
+        // default parameters
+        prop_assert_eq!(m.0, 0);
+    }
+}
+
+#[test]
+fn default_parameters() {
+    fn params<T: Arbitrary<Parameters = P>, P>() {}
+    params::<Even, ()>();
+    params::<Below, u64>();
+}
@@ -0,0 +1,77 @@
+//! Tests the `quickcheck` definitions, run with `cargo test --features quickcheck`.
+
+#![cfg(feature = "quickcheck")]
+
+use implem::implem;
+use quickcheck::{quickcheck, Arbitrary, Gen};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NonEmpty(String);
+impl NonEmpty {
+    pub fn new(s: String) -> Option<Self> {
+        if s.is_empty() {
+            None
+        } else {
+            Some(Self(s))
+        }
+    }
+}
+implem! {
+    for NonEmpty {
+        Default {
+            || Self("?".into())
+        }
+        Arbitrary {
+            |g| Self::new(String::arbitrary(g)).unwrap_or_default(),
+            |&self| Box::new(self.0.shrink().filter_map(Self::new)),
+        }
+    }
+}
+
+/// Without `shrink`.
+#[derive(Debug, Clone, Copy)]
+pub struct Even(u32);
+implem! {
+    for Even {
+        Arbitrary {
+            |g| Self(u32::arbitrary(g) * 2)
+        }
+    }
+}
+
+#[test]
+fn property() {
+    fn never_empty(s: NonEmpty) -> bool {
+        !s.0.is_empty()
+    }
+    quickcheck(never_empty as fn(NonEmpty) -> bool);
+
+    fn shrinks_never_empty(s: NonEmpty) -> bool {
+        s.shrink().all(|s| !s.0.is_empty())
+    }
+    quickcheck(shrinks_never_empty as fn(NonEmpty) -> bool);
+
+    fn even(n: Even) -> bool {
+        n.0 / 2 * 2 == n.0
+    }
+    quickcheck(even as fn(Even) -> bool);
+}
+
+#[test]
+fn shrink() {
+    let shrunk: Vec<_> = NonEmpty("abc".into()).shrink().collect();
+    assert_eq!(shrunk, vec![NonEmpty("a".into()), NonEmpty("ab".into())]);
+    assert_eq!(Even(4).shrink().count(), 0);
+    // generated values can be empty strings, turned into the default
+    let mut g = Gen::new(2);
+    assert!((0..100).any(|_| NonEmpty::arbitrary(&mut g) == NonEmpty::default()));
+}
+
+#[test]
+#[should_panic(expected = "Arguments: (NonEmpty(\"")]
+fn shrunk_counterexample() {
+    fn short(s: NonEmpty) -> bool {
+        s.0.chars().count() < 3
+    }
+    quickcheck(short as fn(NonEmpty) -> bool);
+}
@@ -0,0 +1,50 @@
+//! Tests the `rand` definitions, run with `cargo test --features rand`.
+
+#![cfg(feature = "rand")]
+
+use implem::implem;
+use rand::distributions::{Distribution, Standard};
+use rand::rngs::mock::StepRng;
+use rand::Rng;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Angle(f64);
+implem! {
+    for Angle {
+        RandStandard {
+            |rng| Angle(rng.gen_range(0.0..360.0))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Pair<T>(T, T);
+implem! {
+    impl(T) for Pair<T> where (Standard: Distribution<T>) {
+        RandStandard {
+            |rng| Pair(rng.gen(), rng.gen())
+        }
+    }
+}
+
+#[test]
+fn sample() {
+    let mut rng = StepRng::new(0, 0x0123_4567_89ab_cdef);
+    for _ in 0..100 {
+        let angle: Angle = rng.gen();
+        assert!((0.0..360.0).contains(&angle.0));
+    }
+    let angle: Angle = Standard.sample(&mut StepRng::new(0, 1));
+    assert_eq!(angle.0, 0.0);
+
+    let mut rng = StepRng::new(5, 3);
+    assert_eq!(rng.gen::<Pair<u64>>(), Pair(5, 8));
+    assert_eq!(rng.gen::<Pair<u32>>(), Pair(11, 14));
+}
+
+#[test]
+fn unsized_rng() {
+    let rng: &mut dyn rand::RngCore = &mut StepRng::new(1, 1);
+    let pair: Pair<u64> = Standard.sample(rng);
+    assert_eq!(pair, Pair(1, 2));
+}
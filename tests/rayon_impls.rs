@@ -0,0 +1,34 @@
+//! Tests the `rayon` definitions, run with `cargo test --features rayon`.
+
+#![cfg(feature = "rayon")]
+
+use implem::implem;
+use rayon::prelude::*;
+
+pub struct Bag {
+    items: Vec<u64>,
+}
+implem! {
+    for Bag {
+        IntoParallelIterator<Item = u64, Iter = rayon::vec::IntoIter<u64>> {
+            |self| self.items.into_par_iter()
+        }
+    }
+    impl('a) for &'a Bag {
+        IntoParallelIterator<Item = &'a u64, Iter = rayon::slice::Iter<'a, u64>> {
+            |bag| bag.items.par_iter()
+        }
+    }
+}
+
+#[test]
+fn parallel_sum() {
+    let bag = Bag {
+        items: (1..=100).collect(),
+    };
+    let sequential: u64 = bag.items.iter().sum();
+    assert_eq!(sequential, 5050);
+    assert_eq!(bag.par_iter().sum::<u64>(), sequential);
+    assert_eq!((&bag).into_par_iter().sum::<u64>(), sequential);
+    assert_eq!(bag.into_par_iter().sum::<u64>(), sequential);
+}
@@ -0,0 +1,43 @@
+//! Runs the tests generated by `test_roundtrip!`.
+
+use implem::{implem, test_roundtrip};
+
+#[derive(Debug, PartialEq)]
+pub struct Version {
+    major: u32,
+    minor: u32,
+}
+implem! {
+    for Version {
+        DisplayFromStr {
+            |&self, fmt| write!(fmt, "{}.{}", self.major, self.minor),
+            |s| {
+                let (major, minor) = s.split_once('.').ok_or("expected `.`")?;
+                let parse = |n: &str| n.parse::<u32>().map_err(|e| e.to_string());
+                Ok(Self { major: parse(major)?, minor: parse(minor)? })
+            },
+        }
+    }
+}
+
+test_roundtrip!(
+    Version,
+    Version { major: 0, minor: 0 },
+    Version { major: 1, minor: 12 },
+    Version { major: u32::MAX, minor: 7 },
+);
+
+/// Loses the sign when displayed.
+#[derive(Debug, PartialEq)]
+pub struct Lossy(i32);
+implem! {
+    for Lossy {
+        DisplayFromStr {
+            |&self, fmt| write!(fmt, "{}", self.0.abs()),
+            |s| s.parse().map(Self).map_err(|e: std::num::ParseIntError| e.to_string()),
+        }
+    }
+}
+
+test_roundtrip!(lossy_positive_roundtrip => Lossy, Lossy(0), Lossy(3));
+test_roundtrip!(#[should_panic] lossy_negative_roundtrip => Lossy, Lossy(-3));
@@ -0,0 +1,171 @@
+//! Tests the `serde` definitions, run with `cargo test --features serde`.
+
+#![cfg(feature = "serde")]
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use implem::implem;
+use serde::Serialize;
+
+#[derive(Debug, PartialEq)]
+pub struct Id(u64);
+implem! {
+    for Id {
+        Display {
+            |&self, fmt| write!(fmt, "#{}", self.0)
+        }
+        Serialize {
+            |&self, serializer| serializer.serialize_str(&self.to_string())
+        }
+        Deserialize {
+            |deserializer| {
+                let s = String::deserialize(deserializer)?;
+                let n = s.strip_prefix('#').ok_or_else(|| {
+                    serde::de::Error::custom(format!("`{}` does not start with `#`", s))
+                })?;
+                n.parse().map(Id).map_err(serde::de::Error::custom)
+            }
+        }
+    }
+}
+
+pub struct Wrapper<T>(T);
+implem! {
+    impl(T) for Wrapper<T> where (T: Serialize) {
+        Serialize {
+            |&self, serializer| self.0.serialize(serializer)
+        }
+    }
+}
+
+#[test]
+fn serialize() {
+    assert_eq!(serde_json::to_string(&Id(7)).unwrap(), r##""#7""##);
+    assert_eq!(serde_json::to_string(&Wrapper(3u8)).unwrap(), "3");
+    assert_eq!(
+        serde_json::to_string(&Wrapper("a\"b")).unwrap(),
+        r#""a\"b""#
+    );
+    assert_eq!(serde_json::to_string(&Wrapper(Id(1))).unwrap(), r##""#1""##);
+}
+
+#[test]
+fn deserialize() {
+    let id = Id(42);
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(serde_json::from_str::<Id>(&json).unwrap(), id);
+    let err = serde_json::from_str::<Id>(r#""42""#).unwrap_err();
+    assert_eq!(err.to_string(), "`42` does not start with `#`");
+    assert!(serde_json::from_str::<Id>(r##""#x""##).is_err());
+    assert!(serde_json::from_str::<Id>("42").is_err());
+}
+
+/// Owns or borrows from the input.
+#[derive(Debug, PartialEq)]
+pub struct Name<'a>(Cow<'a, str>);
+implem! {
+    impl('a) for Name<'a> {
+        Serialize {
+            |&self, serializer| self.0.serialize(serializer)
+        }
+        Deserialize {
+            |deserializer| String::deserialize(deserializer).map(|s| Name(Cow::Owned(s)))
+        }
+    }
+}
+
+/// Always borrows from the input.
+#[derive(Debug, PartialEq)]
+pub struct Borrowed<'a>(&'a str);
+implem! {
+    impl('a) for Borrowed<'a> where ('de: 'a) {
+        Deserialize {
+            |deserializer| <&'de str>::deserialize(deserializer).map(Borrowed)
+        }
+    }
+}
+
+#[test]
+fn deserialize_with_lifetimes() {
+    let name: Name<'static> = serde_json::from_str(r#""alice""#).unwrap();
+    assert_eq!(name, Name(Cow::Borrowed("alice")));
+    assert_eq!(serde_json::to_string(&name).unwrap(), r#""alice""#);
+    let escaped: Name = serde_json::from_str(r#""a\"b""#).unwrap();
+    assert_eq!(escaped.0, "a\"b");
+
+    let input = String::from(r#" "bob" "#);
+    let borrowed: Borrowed = serde_json::from_str(&input).unwrap();
+    assert_eq!(borrowed, Borrowed(&input[2..5]));
+    // cannot borrow a string that needs unescaping
+    assert!(serde_json::from_str::<Borrowed>(r#""a\"b""#).is_err());
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Eq, Ord)]
+pub struct Version {
+    major: u32,
+    minor: u32,
+}
+implem! {
+    for Version {
+        DisplayFromStr {
+            |&self, fmt| write!(fmt, "{}.{}", self.major, self.minor),
+            |s| {
+                let (major, minor) = s.split_once('.').ok_or("expected `.`")?;
+                let parse = |n: &str| n.parse::<u32>().map_err(|e| e.to_string());
+                Ok(Self { major: parse(major)?, minor: parse(minor)? })
+            },
+        }
+        SerdeViaStr {}
+    }
+}
+
+pub struct Port(u16);
+implem! {
+    for Port {
+        Display {
+            |&self, fmt| write!(fmt, ":{}", self.0)
+        }
+        SerializeViaDisplay {}
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Level(u8);
+implem! {
+    for Level {
+        FromStr<Err = std::num::ParseIntError> {
+            |s| s.parse().map(Level)
+        }
+        DeserializeViaFromStr {}
+    }
+}
+
+#[test]
+fn serde_via_str() {
+    let version = Version { major: 1, minor: 2 };
+    let json = serde_json::to_string(&version).unwrap();
+    assert_eq!(json, r#""1.2""#);
+    assert_eq!(serde_json::from_str::<Version>(&json).unwrap(), version);
+    let err = serde_json::from_str::<Version>(r#""1""#).unwrap_err();
+    assert_eq!(err.to_string(), "expected `.`");
+
+    assert_eq!(serde_json::to_string(&Port(80)).unwrap(), r#"":80""#);
+    assert_eq!(serde_json::from_str::<Level>(r#""3""#).unwrap(), Level(3));
+    // parses the string form only
+    assert!(serde_json::from_str::<Level>("3").is_err());
+    let err = serde_json::from_str::<Level>(r#""300""#).unwrap_err();
+    assert_eq!(err.to_string(), "number too large to fit in target type");
+}
+
+#[test]
+fn serde_via_str_map_keys() {
+    let mut changes = BTreeMap::new();
+    changes.insert(Version { major: 0, minor: 9 }, "beta".to_string());
+    changes.insert(Version { major: 1, minor: 0 }, "stable".to_string());
+    let json = serde_json::to_string(&changes).unwrap();
+    assert_eq!(json, r#"{"0.9":"beta","1.0":"stable"}"#);
+    let parsed: BTreeMap<Version, String> = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, changes);
+    assert!(serde_json::from_str::<BTreeMap<Version, String>>(r#"{"1":"x"}"#).is_err());
+}
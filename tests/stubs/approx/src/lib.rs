@@ -0,0 +1,142 @@
+//! Minimal stand-in for `approx` 0.5, used by the tests of `implem` which must build offline.
+
+pub trait AbsDiffEq<Rhs = Self>: PartialEq<Rhs>
+where
+    Rhs: ?Sized,
+{
+    type Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon;
+
+    fn abs_diff_eq(&self, other: &Rhs, epsilon: Self::Epsilon) -> bool;
+
+    fn abs_diff_ne(&self, other: &Rhs, epsilon: Self::Epsilon) -> bool {
+        !Self::abs_diff_eq(self, other, epsilon)
+    }
+}
+
+pub trait RelativeEq<Rhs = Self>: AbsDiffEq<Rhs>
+where
+    Rhs: ?Sized,
+{
+    fn default_max_relative() -> Self::Epsilon;
+
+    fn relative_eq(&self, other: &Rhs, epsilon: Self::Epsilon, max_relative: Self::Epsilon)
+        -> bool;
+
+    fn relative_ne(
+        &self,
+        other: &Rhs,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        !Self::relative_eq(self, other, epsilon, max_relative)
+    }
+}
+
+impl AbsDiffEq for f64 {
+    type Epsilon = f64;
+    fn default_epsilon() -> f64 {
+        f64::EPSILON
+    }
+    fn abs_diff_eq(&self, other: &f64, epsilon: f64) -> bool {
+        (self - other).abs() <= epsilon
+    }
+}
+
+impl RelativeEq for f64 {
+    fn default_max_relative() -> f64 {
+        f64::EPSILON
+    }
+    fn relative_eq(&self, other: &f64, epsilon: f64, max_relative: f64) -> bool {
+        if self == other {
+            return true;
+        }
+        if self.is_infinite() || other.is_infinite() {
+            return false;
+        }
+        let abs_diff = (self - other).abs();
+        if abs_diff <= epsilon {
+            return true;
+        }
+        abs_diff <= self.abs().max(other.abs()) * max_relative
+    }
+}
+
+pub struct AbsDiff<A: AbsDiffEq<B> + ?Sized, B: ?Sized = A> {
+    pub epsilon: A::Epsilon,
+}
+impl<A: AbsDiffEq<B> + ?Sized, B: ?Sized> Default for AbsDiff<A, B> {
+    fn default() -> Self {
+        Self {
+            epsilon: A::default_epsilon(),
+        }
+    }
+}
+impl<A: AbsDiffEq<B> + ?Sized, B: ?Sized> AbsDiff<A, B> {
+    pub fn epsilon(self, epsilon: A::Epsilon) -> Self {
+        Self { epsilon }
+    }
+    pub fn eq(self, lhs: &A, rhs: &B) -> bool {
+        A::abs_diff_eq(lhs, rhs, self.epsilon)
+    }
+}
+
+pub struct Relative<A: RelativeEq<B> + ?Sized, B: ?Sized = A> {
+    pub epsilon: A::Epsilon,
+    pub max_relative: A::Epsilon,
+}
+impl<A: RelativeEq<B> + ?Sized, B: ?Sized> Default for Relative<A, B> {
+    fn default() -> Self {
+        Self {
+            epsilon: A::default_epsilon(),
+            max_relative: A::default_max_relative(),
+        }
+    }
+}
+impl<A: RelativeEq<B> + ?Sized, B: ?Sized> Relative<A, B> {
+    pub fn epsilon(self, epsilon: A::Epsilon) -> Self {
+        Self { epsilon, ..self }
+    }
+    pub fn max_relative(self, max_relative: A::Epsilon) -> Self {
+        Self {
+            max_relative,
+            ..self
+        }
+    }
+    pub fn eq(self, lhs: &A, rhs: &B) -> bool {
+        A::relative_eq(lhs, rhs, self.epsilon, self.max_relative)
+    }
+}
+
+#[macro_export]
+macro_rules! assert_abs_diff_eq {
+    ($given:expr, $expected:expr $(, $opt:ident = $val:expr)* $(,)?) => {
+        match (&$given, &$expected) {
+            (given, expected) => {
+                if !$crate::AbsDiff::default() $(.$opt($val))* .eq(given, expected) {
+                    panic!(
+                        "assert_abs_diff_eq!({}, {})\n\n    left  = {:?}\n    right = {:?}\n",
+                        stringify!($given), stringify!($expected), given, expected,
+                    )
+                }
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! assert_relative_eq {
+    ($given:expr, $expected:expr $(, $opt:ident = $val:expr)* $(,)?) => {
+        match (&$given, &$expected) {
+            (given, expected) => {
+                if !$crate::Relative::default() $(.$opt($val))* .eq(given, expected) {
+                    panic!(
+                        "assert_relative_eq!({}, {})\n\n    left  = {:?}\n    right = {:?}\n",
+                        stringify!($given), stringify!($expected), given, expected,
+                    )
+                }
+            }
+        }
+    };
+}
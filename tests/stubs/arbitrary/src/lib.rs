@@ -0,0 +1,100 @@
+//! Minimal stand-in for `arbitrary` 1.x, used by the tests of `implem` which must build offline.
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    EmptyChoose,
+    NotEnoughData,
+    IncorrectFormat,
+}
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::EmptyChoose => fmt.write_str("`choose` called with an empty slice"),
+            Error::NotEnoughData => fmt.write_str("not enough data"),
+            Error::IncorrectFormat => fmt.write_str("incorrect format"),
+        }
+    }
+}
+impl std::error::Error for Error {}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+pub struct Unstructured<'a> {
+    data: &'a [u8],
+}
+impl<'a> Unstructured<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    pub fn arbitrary<A: Arbitrary<'a>>(&mut self) -> Result<A> {
+        A::arbitrary(self)
+    }
+    pub fn bytes(&mut self, size: usize) -> Result<&'a [u8]> {
+        if self.data.len() < size {
+            return Err(Error::NotEnoughData);
+        }
+        let (bytes, rest) = self.data.split_at(size);
+        self.data = rest;
+        Ok(bytes)
+    }
+    /// Takes up to `buf.len()` bytes, the rest of `buf` is zeroed.
+    pub fn fill_buffer(&mut self, buf: &mut [u8]) -> Result<()> {
+        let n = buf.len().min(self.data.len());
+        let (bytes, rest) = self.data.split_at(n);
+        buf[..n].copy_from_slice(bytes);
+        buf[n..].iter_mut().for_each(|b| *b = 0);
+        self.data = rest;
+        Ok(())
+    }
+}
+
+pub trait Arbitrary<'a>: Sized {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self>;
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        let _ = depth;
+        (0, None)
+    }
+}
+
+macro_rules! impl_ints {
+    ($($ty:ty)*) => {$(
+        impl<'a> Arbitrary<'a> for $ty {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                let mut buf = [0; std::mem::size_of::<$ty>()];
+                u.fill_buffer(&mut buf)?;
+                Ok(<$ty>::from_le_bytes(buf))
+            }
+            fn size_hint(_: usize) -> (usize, Option<usize>) {
+                let n = std::mem::size_of::<$ty>();
+                (n, Some(n))
+            }
+        }
+    )*};
+}
+impl_ints! { u8 u16 u32 u64 i8 i16 i32 i64 }
+
+impl<'a> Arbitrary<'a> for bool {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(u8::arbitrary(u)? & 1 == 1)
+    }
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u8::size_hint(depth)
+    }
+}
+
+pub mod size_hint {
+    pub fn and(lhs: (usize, Option<usize>), rhs: (usize, Option<usize>)) -> (usize, Option<usize>) {
+        let upper = lhs.1.and_then(|lhs| rhs.1.map(|rhs| lhs + rhs));
+        (lhs.0 + rhs.0, upper)
+    }
+}
@@ -0,0 +1,99 @@
+//! Minimal stand-in for `borsh` 1.x, used by the tests of `implem` which must build offline.
+
+use std::convert::TryFrom;
+use std::io::{self, Read, Write};
+
+pub trait BorshSerialize {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+}
+
+pub trait BorshDeserialize: Sized {
+    fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
+        Self::deserialize_reader(&mut *buf)
+    }
+
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self>;
+
+    fn try_from_slice(v: &[u8]) -> io::Result<Self> {
+        let mut v_mut = v;
+        let res = Self::deserialize(&mut v_mut)?;
+        if !v_mut.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Not all bytes read",
+            ));
+        }
+        Ok(res)
+    }
+}
+
+pub fn to_vec<T: BorshSerialize + ?Sized>(value: &T) -> io::Result<Vec<u8>> {
+    let mut res = Vec::new();
+    value.serialize(&mut res)?;
+    Ok(res)
+}
+
+pub fn from_slice<T: BorshDeserialize>(v: &[u8]) -> io::Result<T> {
+    T::try_from_slice(v)
+}
+
+macro_rules! impl_ints {
+    ($($ty:ty)*) => {$(
+        impl BorshSerialize for $ty {
+            fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                writer.write_all(&self.to_le_bytes())
+            }
+        }
+        impl BorshDeserialize for $ty {
+            fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+                let mut buf = [0; std::mem::size_of::<$ty>()];
+                reader.read_exact(&mut buf)?;
+                Ok(<$ty>::from_le_bytes(buf))
+            }
+        }
+    )*};
+}
+impl_ints! { u8 u16 u32 u64 i32 i64 }
+
+impl<T: BorshSerialize> BorshSerialize for [T] {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let len = u32::try_from(self.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "slice too long"))?;
+        len.serialize(writer)?;
+        self.iter().try_for_each(|item| item.serialize(writer))
+    }
+}
+impl<T: BorshSerialize> BorshSerialize for Vec<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self[..].serialize(writer)
+    }
+}
+impl<T: BorshDeserialize> BorshDeserialize for Vec<T> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let len = u32::deserialize_reader(reader)?;
+        (0..len).map(|_| T::deserialize_reader(reader)).collect()
+    }
+}
+
+impl BorshSerialize for str {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.as_bytes().serialize(writer)
+    }
+}
+impl BorshSerialize for String {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.as_str().serialize(writer)
+    }
+}
+impl BorshDeserialize for String {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        String::from_utf8(Vec::deserialize_reader(reader)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl<T: BorshSerialize + ?Sized> BorshSerialize for &T {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (**self).serialize(writer)
+    }
+}
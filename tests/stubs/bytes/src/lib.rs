@@ -0,0 +1,104 @@
+//! Minimal stand-in for `bytes` 1.x, used by the tests of `implem` which must build offline.
+
+pub use crate::buf::{Buf, BufMut};
+
+pub mod buf {
+    use std::mem::MaybeUninit;
+
+    #[repr(transparent)]
+    pub struct UninitSlice([MaybeUninit<u8>]);
+    impl UninitSlice {
+        pub fn new(slice: &mut [u8]) -> &mut UninitSlice {
+            // SAFETY: `UninitSlice` is a transparent wrapper around `[MaybeUninit<u8>]`, which has
+            // the same layout as `[u8]`.
+            unsafe { &mut *(slice as *mut [u8] as *mut UninitSlice) }
+        }
+        pub fn len(&self) -> usize {
+            self.0.len()
+        }
+        pub fn is_empty(&self) -> bool {
+            self.0.is_empty()
+        }
+        pub fn as_mut_ptr(&mut self) -> *mut u8 {
+            self.0.as_mut_ptr() as *mut u8
+        }
+        pub fn copy_from_slice(&mut self, src: &[u8]) {
+            assert_eq!(self.len(), src.len());
+            for (dst, src) in self.0.iter_mut().zip(src) {
+                *dst = MaybeUninit::new(*src);
+            }
+        }
+    }
+
+    pub trait Buf {
+        fn remaining(&self) -> usize;
+        fn chunk(&self) -> &[u8];
+        fn advance(&mut self, cnt: usize);
+
+        fn has_remaining(&self) -> bool {
+            self.remaining() > 0
+        }
+
+        fn copy_to_slice(&mut self, dst: &mut [u8]) {
+            assert!(self.remaining() >= dst.len(), "buffer too short");
+            let mut off = 0;
+            while off < dst.len() {
+                let src = self.chunk();
+                let cnt = src.len().min(dst.len() - off);
+                dst[off..off + cnt].copy_from_slice(&src[..cnt]);
+                off += cnt;
+                self.advance(cnt);
+            }
+        }
+
+        fn get_u8(&mut self) -> u8 {
+            let mut buf = [0; 1];
+            self.copy_to_slice(&mut buf);
+            buf[0]
+        }
+
+        fn get_u32(&mut self) -> u32 {
+            let mut buf = [0; 4];
+            self.copy_to_slice(&mut buf);
+            u32::from_be_bytes(buf)
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `advance_mut` must only be called with a `cnt` of at most the length of the last
+    /// `chunk_mut`, all of whose first `cnt` bytes are initialized.
+    pub unsafe trait BufMut {
+        fn remaining_mut(&self) -> usize;
+        /// # Safety
+        ///
+        /// The first `cnt` bytes of `chunk_mut` must be initialized.
+        unsafe fn advance_mut(&mut self, cnt: usize);
+        fn chunk_mut(&mut self) -> &mut UninitSlice;
+
+        fn has_remaining_mut(&self) -> bool {
+            self.remaining_mut() > 0
+        }
+
+        fn put_slice(&mut self, mut src: &[u8]) {
+            assert!(self.remaining_mut() >= src.len(), "buffer overflow");
+            while !src.is_empty() {
+                let dst = self.chunk_mut();
+                let cnt = dst.len().min(src.len());
+                unsafe {
+                    std::ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), cnt);
+                    self.advance_mut(cnt);
+                }
+                src = &src[cnt..];
+            }
+        }
+
+        fn put_u8(&mut self, n: u8) {
+            self.put_slice(&[n])
+        }
+
+        fn put_u32(&mut self, n: u32) {
+            self.put_slice(&n.to_be_bytes())
+        }
+    }
+}
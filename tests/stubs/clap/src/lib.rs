@@ -0,0 +1,211 @@
+//! Minimal stand-in for `clap` 4.x, used by the tests of `implem` which must build offline.
+//!
+//! Only supports `--long value` and `--long=value` arguments parsed by an `EnumValueParser`.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+
+pub use crate::builder::{Arg, Command, ValueEnum};
+
+#[derive(Debug)]
+pub struct Error(String);
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(&self.0)
+    }
+}
+impl std::error::Error for Error {}
+
+/// Only supports types implementing `ValueEnum`.
+#[macro_export]
+macro_rules! value_parser {
+    ($ty:ty) => {
+        $crate::builder::EnumValueParser::<$ty>::new()
+    };
+}
+
+pub mod builder {
+    use std::any::Any;
+    use std::marker::PhantomData;
+
+    use crate::{ArgMatches, Error};
+
+    pub trait ValueEnum: Sized + Clone {
+        fn value_variants<'a>() -> &'a [Self];
+
+        fn from_str(input: &str, ignore_case: bool) -> Result<Self, String> {
+            Self::value_variants()
+                .iter()
+                .find(|v| {
+                    v.to_possible_value()
+                        .expect("ValueEnum::value_variants contains only values with a corresponding ValueEnum::to_possible_value")
+                        .matches(input, ignore_case)
+                })
+                .cloned()
+                .ok_or_else(|| format!("invalid variant: {}", input))
+        }
+
+        fn to_possible_value(&self) -> Option<PossibleValue>;
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct PossibleValue {
+        name: &'static str,
+        aliases: Vec<&'static str>,
+    }
+    impl PossibleValue {
+        pub fn new(name: &'static str) -> Self {
+            Self {
+                name,
+                aliases: vec![],
+            }
+        }
+        pub fn alias(mut self, name: &'static str) -> Self {
+            self.aliases.push(name);
+            self
+        }
+        pub fn get_name(&self) -> &str {
+            self.name
+        }
+        pub fn matches(&self, value: &str, ignore_case: bool) -> bool {
+            std::iter::once(&self.name)
+                .chain(&self.aliases)
+                .any(|name| {
+                    if ignore_case {
+                        name.eq_ignore_ascii_case(value)
+                    } else {
+                        *name == value
+                    }
+                })
+        }
+    }
+
+    pub struct EnumValueParser<E>(PhantomData<E>);
+    impl<E: ValueEnum> EnumValueParser<E> {
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    trait AnyValueParser {
+        fn parse(&self, value: &str) -> Result<Box<dyn Any>, String>;
+    }
+    impl<E: ValueEnum + 'static> AnyValueParser for EnumValueParser<E> {
+        fn parse(&self, value: &str) -> Result<Box<dyn Any>, String> {
+            let values: Vec<_> = E::value_variants()
+                .iter()
+                .filter_map(E::to_possible_value)
+                .collect();
+            E::value_variants()
+                .iter()
+                .zip(&values)
+                .find(|(_, possible)| possible.matches(value, false))
+                .map(|(variant, _)| Box::new(variant.clone()) as Box<dyn Any>)
+                .ok_or_else(|| {
+                    let names: Vec<_> = values.iter().map(PossibleValue::get_name).collect();
+                    format!("[possible values: {}]", names.join(", "))
+                })
+        }
+    }
+
+    pub struct ValueParser(Box<dyn AnyValueParser>);
+    impl<E: ValueEnum + 'static> From<EnumValueParser<E>> for ValueParser {
+        fn from(parser: EnumValueParser<E>) -> Self {
+            Self(Box::new(parser))
+        }
+    }
+
+    pub struct Arg {
+        id: &'static str,
+        long: Option<&'static str>,
+        parser: Option<ValueParser>,
+    }
+    impl Arg {
+        pub fn new(id: &'static str) -> Self {
+            Self {
+                id,
+                long: None,
+                parser: None,
+            }
+        }
+        pub fn long(mut self, long: &'static str) -> Self {
+            self.long = Some(long);
+            self
+        }
+        pub fn value_parser(mut self, parser: impl Into<ValueParser>) -> Self {
+            self.parser = Some(parser.into());
+            self
+        }
+    }
+
+    pub struct Command {
+        name: &'static str,
+        args: Vec<Arg>,
+    }
+    impl Command {
+        pub fn new(name: &'static str) -> Self {
+            Self { name, args: vec![] }
+        }
+        pub fn arg(mut self, arg: Arg) -> Self {
+            self.args.push(arg);
+            self
+        }
+        pub fn get_matches_from<I, T>(self, args: I) -> ArgMatches
+        where
+            I: IntoIterator<Item = T>,
+            T: Into<String>,
+        {
+            self.try_get_matches_from(args)
+                .unwrap_or_else(|e| panic!("{}", e))
+        }
+        pub fn try_get_matches_from<I, T>(self, args: I) -> Result<ArgMatches, Error>
+        where
+            I: IntoIterator<Item = T>,
+            T: Into<String>,
+        {
+            let mut matches = ArgMatches::default();
+            let mut args = args.into_iter().map(Into::into).skip(1);
+            while let Some(arg) = args.next() {
+                let unexpected = || Error(format!("{}: unexpected argument '{}'", self.name, arg));
+                let long = arg.strip_prefix("--").ok_or_else(unexpected)?;
+                let (long, value) = match long.split_once('=') {
+                    Some((long, value)) => (long, Some(value.to_string())),
+                    None => (long, None),
+                };
+                let def = self
+                    .args
+                    .iter()
+                    .find(|def| def.long == Some(long))
+                    .ok_or_else(unexpected)?;
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or_else(|| Error(format!("a value is required for '--{}'", long)))?;
+                let parser = def.parser.as_ref().expect("arguments need a value parser");
+                let value = parser.0.parse(&value).map_err(|possible| {
+                    Error(format!(
+                        "invalid value '{}' for '--{}'\n  {}",
+                        value, long, possible
+                    ))
+                })?;
+                matches.values.insert(def.id, value);
+            }
+            Ok(matches)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ArgMatches {
+    values: BTreeMap<&'static str, Box<dyn Any>>,
+}
+impl ArgMatches {
+    pub fn get_one<T: Any + Clone + Send + Sync + 'static>(&self, id: &str) -> Option<&T> {
+        self.values.get(id).map(|value| {
+            value
+                .downcast_ref()
+                .expect("mismatch between the value parser and `get_one`'s type")
+        })
+    }
+}
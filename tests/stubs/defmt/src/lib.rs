@@ -0,0 +1,40 @@
+//! Minimal stand-in for `defmt` 0.3, used by the tests of `implem` which must build offline.
+//!
+//! Nothing is ever logged: `Formatter` cannot be built, and `write!` only type-checks its
+//! arguments.
+
+use std::marker::PhantomData;
+
+pub struct Formatter<'a> {
+    _lifetime: PhantomData<&'a ()>,
+}
+
+pub trait Format {
+    fn format(&self, fmt: Formatter<'_>);
+}
+
+impl Format for f32 {
+    fn format(&self, _: Formatter<'_>) {}
+}
+impl Format for u32 {
+    fn format(&self, _: Formatter<'_>) {}
+}
+impl Format for str {
+    fn format(&self, _: Formatter<'_>) {}
+}
+impl<T: Format + ?Sized> Format for &T {
+    fn format(&self, _: Formatter<'_>) {}
+}
+
+#[doc(hidden)]
+pub fn check_arg<T: Format + ?Sized>(_: &T) {}
+
+/// Checks that the formatter is a `Formatter` and that the arguments implement `Format`.
+#[macro_export]
+macro_rules! write {
+    ($fmt:expr, $str:literal $(, $arg:expr)* $(,)?) => {{
+        let _: $crate::Formatter<'_> = $fmt;
+        let _: &str = $str;
+        $($crate::check_arg(&$arg);)*
+    }};
+}
@@ -0,0 +1,135 @@
+//! Minimal stand-in for `futures` 0.3, used by the tests of `implem` which must build offline.
+
+pub use sink::{Sink, SinkExt};
+pub use stream::{Stream, StreamExt};
+
+pub mod stream {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    pub use futures_core::stream::Stream;
+
+    pub trait StreamExt: Stream {
+        fn next(&mut self) -> Next<'_, Self>
+        where
+            Self: Unpin,
+        {
+            Next { stream: self }
+        }
+    }
+    impl<S: ?Sized + Stream> StreamExt for S {}
+
+    pub struct Next<'a, S: ?Sized> {
+        stream: &'a mut S,
+    }
+    impl<S: ?Sized + Stream + Unpin> Future for Next<'_, S> {
+        type Output = Option<S::Item>;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut *self.stream).poll_next(cx)
+        }
+    }
+}
+
+pub mod sink {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    pub use futures_sink::Sink;
+
+    pub trait SinkExt<Item>: Sink<Item> {
+        fn send(&mut self, item: Item) -> Send<'_, Self, Item>
+        where
+            Self: Unpin,
+        {
+            Send {
+                sink: self,
+                item: Some(item),
+            }
+        }
+        fn close(&mut self) -> Close<'_, Self, Item>
+        where
+            Self: Unpin,
+        {
+            Close {
+                sink: self,
+                _item: std::marker::PhantomData,
+            }
+        }
+    }
+    impl<T: ?Sized + Sink<Item>, Item> SinkExt<Item> for T {}
+
+    pub struct Send<'a, S: ?Sized, Item> {
+        sink: &'a mut S,
+        item: Option<Item>,
+    }
+    impl<S: ?Sized + Unpin, Item> Unpin for Send<'_, S, Item> {}
+    impl<S: ?Sized + Sink<Item> + Unpin, Item> Future for Send<'_, S, Item> {
+        type Output = Result<(), S::Error>;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = &mut *self;
+            if this.item.is_some() {
+                match Pin::new(&mut *this.sink).poll_ready(cx) {
+                    Poll::Ready(Ok(())) => (),
+                    other => return other,
+                }
+                let item = this.item.take().unwrap();
+                Pin::new(&mut *this.sink).start_send(item)?;
+            }
+            Pin::new(&mut *this.sink).poll_flush(cx)
+        }
+    }
+
+    pub struct Close<'a, S: ?Sized, Item> {
+        sink: &'a mut S,
+        _item: std::marker::PhantomData<fn(Item)>,
+    }
+    impl<S: ?Sized + Sink<Item> + Unpin, Item> Future for Close<'_, S, Item> {
+        type Output = Result<(), S::Error>;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut *self.sink).poll_close(cx)
+        }
+    }
+}
+
+pub mod executor {
+    use std::future::Future;
+    use std::task::{Context, Poll, Waker};
+
+    use crate::stream::{Stream, StreamExt};
+
+    /// Busy-polls `fut` with a no-op waker.
+    pub fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = Box::pin(fut);
+        let mut cx = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(res) = fut.as_mut().poll(&mut cx) {
+                return res;
+            }
+            std::thread::yield_now();
+        }
+    }
+
+    pub fn block_on_stream<S: Stream + Unpin>(stream: S) -> BlockingStream<S> {
+        BlockingStream { stream }
+    }
+
+    pub struct BlockingStream<S> {
+        stream: S,
+    }
+    impl<S: Stream + Unpin> Iterator for BlockingStream<S> {
+        type Item = S::Item;
+        fn next(&mut self) -> Option<S::Item> {
+            block_on(self.stream.next())
+        }
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            self.stream.size_hint()
+        }
+    }
+    impl<S: Stream + Unpin> BlockingStream<S> {
+        pub fn into_inner(self) -> S {
+            self.stream
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Minimal stand-in for `num-traits` 0.2, used by the tests of `implem` which must build offline.
+
+use std::convert::TryFrom;
+use std::ops::{Add, Mul};
+
+pub use cast::{FromPrimitive, ToPrimitive};
+pub use identities::{One, Zero};
+
+pub mod identities {
+    use super::*;
+
+    pub trait Zero: Sized + Add<Self, Output = Self> {
+        fn zero() -> Self;
+
+        fn set_zero(&mut self) {
+            *self = Zero::zero();
+        }
+
+        fn is_zero(&self) -> bool;
+    }
+
+    pub trait One: Sized + Mul<Self, Output = Self> {
+        fn one() -> Self;
+
+        fn set_one(&mut self) {
+            *self = One::one();
+        }
+
+        fn is_one(&self) -> bool
+        where
+            Self: PartialEq,
+        {
+            *self == Self::one()
+        }
+    }
+
+    macro_rules! identities {
+        ($($ty:ty: $zero:expr, $one:expr;)*) => {$(
+            impl Zero for $ty {
+                fn zero() -> Self {
+                    $zero
+                }
+                fn is_zero(&self) -> bool {
+                    *self == $zero
+                }
+            }
+            impl One for $ty {
+                fn one() -> Self {
+                    $one
+                }
+            }
+        )*};
+    }
+    identities! {
+        u32: 0, 1;
+        u64: 0, 1;
+        i64: 0, 1;
+        f64: 0.0, 1.0;
+    }
+}
+
+pub mod cast {
+    use super::*;
+
+    pub trait FromPrimitive: Sized {
+        fn from_i8(n: i8) -> Option<Self> {
+            FromPrimitive::from_i64(n.into())
+        }
+        fn from_i32(n: i32) -> Option<Self> {
+            FromPrimitive::from_i64(n.into())
+        }
+        fn from_i64(n: i64) -> Option<Self>;
+        fn from_u8(n: u8) -> Option<Self> {
+            FromPrimitive::from_u64(n.into())
+        }
+        fn from_u32(n: u32) -> Option<Self> {
+            FromPrimitive::from_u64(n.into())
+        }
+        fn from_u64(n: u64) -> Option<Self>;
+        fn from_f64(n: f64) -> Option<Self> {
+            match n.to_i64() {
+                Some(i) => FromPrimitive::from_i64(i),
+                None => n.to_u64().and_then(FromPrimitive::from_u64),
+            }
+        }
+    }
+
+    pub trait ToPrimitive {
+        fn to_i8(&self) -> Option<i8> {
+            self.to_i64().and_then(|n| i8::try_from(n).ok())
+        }
+        fn to_i32(&self) -> Option<i32> {
+            self.to_i64().and_then(|n| i32::try_from(n).ok())
+        }
+        fn to_i64(&self) -> Option<i64>;
+        fn to_u8(&self) -> Option<u8> {
+            self.to_u64().and_then(|n| u8::try_from(n).ok())
+        }
+        fn to_u32(&self) -> Option<u32> {
+            self.to_u64().and_then(|n| u32::try_from(n).ok())
+        }
+        fn to_u64(&self) -> Option<u64>;
+        fn to_f64(&self) -> Option<f64> {
+            match self.to_i64() {
+                Some(i) => Some(i as f64),
+                None => self.to_u64().map(|u| u as f64),
+            }
+        }
+    }
+
+    impl ToPrimitive for f64 {
+        fn to_i64(&self) -> Option<i64> {
+            let in_range = *self > i64::MIN as f64 - 1.0 && *self < i64::MAX as f64;
+            if in_range {
+                Some(*self as i64)
+            } else {
+                None
+            }
+        }
+        fn to_u64(&self) -> Option<u64> {
+            if *self > -1.0 && *self < u64::MAX as f64 {
+                Some(*self as u64)
+            } else {
+                None
+            }
+        }
+        fn to_f64(&self) -> Option<f64> {
+            Some(*self)
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Minimal stand-in for `proptest` 1.x, used by the tests of `implem` which must build offline.
+//!
+//! Strategies generate values directly instead of value trees, so nothing is shrunk.
+
+pub mod prelude {
+    pub use crate::arbitrary::{any, Arbitrary};
+    pub use crate::strategy::{BoxedStrategy, Just, Strategy};
+    pub use crate::{prop_assert, prop_assert_eq, proptest};
+}
+
+pub mod test_runner {
+    use std::fmt;
+
+    pub struct TestRunner {
+        state: u64,
+        cases: u32,
+    }
+    impl Default for TestRunner {
+        fn default() -> Self {
+            Self {
+                state: 0x9e37_79b9_7f4a_7c15,
+                cases: 256,
+            }
+        }
+    }
+    impl TestRunner {
+        pub fn cases(&self) -> u32 {
+            self.cases
+        }
+        #[doc(hidden)]
+        pub fn next_u64(&mut self) -> u64 {
+            self.state ^= self.state << 13;
+            self.state ^= self.state >> 7;
+            self.state ^= self.state << 17;
+            self.state
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct TestCaseError(String);
+    impl TestCaseError {
+        pub fn fail(reason: impl Into<String>) -> Self {
+            Self(reason.into())
+        }
+    }
+    impl fmt::Display for TestCaseError {
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str(&self.0)
+        }
+    }
+}
+
+pub mod strategy {
+    use std::fmt::{self, Debug};
+    use std::sync::Arc;
+
+    use crate::test_runner::TestRunner;
+
+    pub trait Strategy: Debug {
+        type Value: Debug;
+
+        #[doc(hidden)]
+        fn new_value(&self, runner: &mut TestRunner) -> Self::Value;
+
+        fn prop_map<O: Debug, F: Fn(Self::Value) -> O>(self, fun: F) -> Map<Self, F>
+        where
+            Self: Sized,
+        {
+            Map { source: self, fun }
+        }
+
+        fn boxed(self) -> BoxedStrategy<Self::Value>
+        where
+            Self: Sized + 'static,
+        {
+            BoxedStrategy(Arc::new(self))
+        }
+    }
+
+    pub struct Map<S, F> {
+        source: S,
+        fun: F,
+    }
+    impl<S: Debug, F> Debug for Map<S, F> {
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.debug_struct("Map")
+                .field("source", &self.source)
+                .finish()
+        }
+    }
+    impl<S: Strategy, O: Debug, F: Fn(S::Value) -> O> Strategy for Map<S, F> {
+        type Value = O;
+        fn new_value(&self, runner: &mut TestRunner) -> O {
+            (self.fun)(self.source.new_value(runner))
+        }
+    }
+
+    pub struct BoxedStrategy<T>(Arc<dyn Strategy<Value = T>>);
+    impl<T> Debug for BoxedStrategy<T> {
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str("BoxedStrategy")
+        }
+    }
+    impl<T> Clone for BoxedStrategy<T> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+    impl<T: Debug> Strategy for BoxedStrategy<T> {
+        type Value = T;
+        fn new_value(&self, runner: &mut TestRunner) -> T {
+            self.0.new_value(runner)
+        }
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct Just<T: Clone + Debug>(pub T);
+    impl<T: Clone + Debug> Strategy for Just<T> {
+        type Value = T;
+        fn new_value(&self, _: &mut TestRunner) -> T {
+            self.0.clone()
+        }
+    }
+
+    impl Strategy for std::ops::Range<u64> {
+        type Value = u64;
+        fn new_value(&self, runner: &mut TestRunner) -> u64 {
+            assert!(self.start < self.end, "empty range");
+            self.start + runner.next_u64() % (self.end - self.start)
+        }
+    }
+}
+
+pub mod arbitrary {
+    use std::fmt::Debug;
+
+    use crate::strategy::Strategy;
+    use crate::test_runner::TestRunner;
+
+    pub trait Arbitrary: Sized + Debug {
+        type Parameters: Default;
+        type Strategy: Strategy<Value = Self>;
+
+        fn arbitrary() -> Self::Strategy {
+            Self::arbitrary_with(Default::default())
+        }
+
+        fn arbitrary_with(args: Self::Parameters) -> Self::Strategy;
+    }
+
+    pub fn any<A: Arbitrary>() -> A::Strategy {
+        A::arbitrary()
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub struct AnyU64;
+    impl Strategy for AnyU64 {
+        type Value = u64;
+        fn new_value(&self, runner: &mut TestRunner) -> u64 {
+            runner.next_u64()
+        }
+    }
+    impl Arbitrary for u64 {
+        type Parameters = ();
+        type Strategy = AnyU64;
+        fn arbitrary_with(_: ()) -> AnyU64 {
+            AnyU64
+        }
+    }
+}
+
+/// Supports `#[test]` functions whose arguments are `pat in strategy` or `name: Type`.
+#[macro_export]
+macro_rules! proptest {
+    () => {};
+    ($(#[$meta:meta])* fn $name:ident($($args:tt)*) $body:block $($rest:tt)*) => {
+        $(#[$meta])*
+        fn $name() {
+            let mut runner = $crate::test_runner::TestRunner::default();
+            for _ in 0..runner.cases() {
+                $crate::__proptest_args!(runner, $($args)*);
+                let res: ::std::result::Result<(), $crate::test_runner::TestCaseError> =
+                    (|| {
+                        $body;
+                        Ok(())
+                    })();
+                if let Err(e) = res {
+                    panic!("Test failed: {}", e)
+                }
+            }
+        }
+        $crate::proptest! { $($rest)* }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __proptest_args {
+    ($runner:ident $(,)?) => {};
+    ($runner:ident, $name:ident: $ty:ty $(, $($rest:tt)*)?) => {
+        let $name: $ty = $crate::strategy::Strategy::new_value(
+            &$crate::arbitrary::any::<$ty>(),
+            &mut $runner,
+        );
+        $crate::__proptest_args!($runner, $($($rest)*)?);
+    };
+    ($runner:ident, $pat:pat in $strategy:expr $(, $($rest:tt)*)?) => {
+        let $pat = $crate::strategy::Strategy::new_value(&$strategy, &mut $runner);
+        $crate::__proptest_args!($runner, $($($rest)*)?);
+    };
+}
+
+#[macro_export]
+macro_rules! prop_assert {
+    ($cond:expr $(,)?) => {
+        if !$cond {
+            return Err($crate::test_runner::TestCaseError::fail(concat!(
+                "assertion failed: ",
+                stringify!($cond)
+            )));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! prop_assert_eq {
+    ($lft:expr, $rgt:expr $(,)?) => {
+        match (&$lft, &$rgt) {
+            (lft, rgt) => {
+                if lft != rgt {
+                    return Err($crate::test_runner::TestCaseError::fail(format!(
+                        "assertion failed: `{:?} == {:?}`",
+                        lft, rgt
+                    )));
+                }
+            }
+        }
+    };
+}
@@ -0,0 +1,141 @@
+//! Minimal stand-in for `quickcheck` 1.x, used by the tests of `implem` which must build offline.
+//!
+//! Generation is deterministic, failing inputs are shrunk greedily.
+
+use std::fmt::Debug;
+
+/// Generates values from a xorshift sequence.
+pub struct Gen {
+    state: u64,
+    size: usize,
+}
+impl Gen {
+    pub fn new(size: usize) -> Self {
+        Self {
+            state: 0x2545_f491_4f6c_dd1d,
+            size,
+        }
+    }
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            None
+        } else {
+            let idx = self.next_u64() as usize % slice.len();
+            slice.get(idx)
+        }
+    }
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+}
+
+pub fn empty_shrinker<A: 'static>() -> Box<dyn Iterator<Item = A>> {
+    Box::new(std::iter::empty())
+}
+
+pub fn single_shrinker<A: 'static>(value: A) -> Box<dyn Iterator<Item = A>> {
+    Box::new(std::iter::once(value))
+}
+
+pub trait Arbitrary: Clone + 'static {
+    fn arbitrary(g: &mut Gen) -> Self;
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        empty_shrinker()
+    }
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(g: &mut Gen) -> Self {
+        g.next_u64() % 2 == 0
+    }
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        if *self {
+            single_shrinker(false)
+        } else {
+            empty_shrinker()
+        }
+    }
+}
+
+impl Arbitrary for u32 {
+    fn arbitrary(g: &mut Gen) -> Self {
+        (g.next_u64() % (g.size() as u64 + 1)) as u32
+    }
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let n = *self;
+        Box::new(
+            (0..n.min(8))
+                .chain(std::iter::once(n / 2))
+                .filter(move |m| *m < n),
+        )
+    }
+}
+
+impl Arbitrary for char {
+    fn arbitrary(g: &mut Gen) -> Self {
+        *g.choose(&['a', 'b', 'z', 'A', '0', ' ', 'é', '\n'])
+            .unwrap()
+    }
+}
+
+impl Arbitrary for String {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let len = g.next_u64() as usize % (g.size() + 1);
+        (0..len).map(|_| char::arbitrary(g)).collect()
+    }
+    /// Shorter prefixes, the empty string first.
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let chars: Vec<char> = self.chars().collect();
+        Box::new((0..chars.len()).map(move |len| chars[..len].iter().collect()))
+    }
+}
+
+pub trait Testable: 'static {
+    /// Checks the property, returns the shrunk failing input if any.
+    fn check(&self, g: &mut Gen) -> Result<(), String>;
+}
+
+impl Testable for bool {
+    fn check(&self, _: &mut Gen) -> Result<(), String> {
+        if *self {
+            Ok(())
+        } else {
+            Err("()".into())
+        }
+    }
+}
+
+impl<A: Arbitrary + Debug> Testable for fn(A) -> bool {
+    fn check(&self, g: &mut Gen) -> Result<(), String> {
+        let mut input = A::arbitrary(g);
+        if self(input.clone()) {
+            return Ok(());
+        }
+        'shrinking: loop {
+            for smaller in input.shrink() {
+                if !self(smaller.clone()) {
+                    input = smaller;
+                    continue 'shrinking;
+                }
+            }
+            return Err(format!("({:?})", input));
+        }
+    }
+}
+
+/// Checks `f` on 100 generated inputs, panics on the first failure.
+pub fn quickcheck<A: Testable>(f: A) {
+    let mut g = Gen::new(100);
+    for _ in 0..100 {
+        if let Err(args) = f.check(&mut g) {
+            panic!("[quickcheck] TEST FAILED. Arguments: {}", args)
+        }
+    }
+}
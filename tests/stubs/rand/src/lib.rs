@@ -0,0 +1,121 @@
+//! Minimal stand-in for `rand` 0.8, used by the tests of `implem` which must build offline.
+
+pub use crate::rng::Rng;
+pub use crate::rng_core::RngCore;
+
+pub mod rng_core {
+    pub trait RngCore {
+        fn next_u32(&mut self) -> u32;
+        fn next_u64(&mut self) -> u64;
+    }
+    impl<R: RngCore + ?Sized> RngCore for &mut R {
+        fn next_u32(&mut self) -> u32 {
+            (**self).next_u32()
+        }
+        fn next_u64(&mut self) -> u64 {
+            (**self).next_u64()
+        }
+    }
+}
+
+pub mod distributions {
+    use crate::Rng;
+
+    pub trait Distribution<T> {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T;
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    pub struct Standard;
+
+    impl Distribution<u32> for Standard {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u32 {
+            rng.next_u32()
+        }
+    }
+    impl Distribution<u64> for Standard {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+            rng.next_u64()
+        }
+    }
+    impl Distribution<bool> for Standard {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
+            (rng.next_u32() as i32) < 0
+        }
+    }
+    /// Uniform in `[0, 1)`.
+    impl Distribution<f64> for Standard {
+        fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+            (rng.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+        }
+    }
+}
+
+pub mod rng {
+    use std::ops::Range;
+
+    use crate::distributions::{Distribution, Standard};
+    use crate::RngCore;
+
+    pub trait SampleRange<T> {
+        fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> T;
+    }
+    impl SampleRange<u32> for Range<u32> {
+        fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> u32 {
+            assert!(self.start < self.end, "cannot sample empty range");
+            self.start + rng.next_u32() % (self.end - self.start)
+        }
+    }
+    impl SampleRange<f64> for Range<f64> {
+        fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> f64 {
+            assert!(self.start < self.end, "cannot sample empty range");
+            let unit: f64 = Standard.sample(&mut *rng);
+            self.start + (self.end - self.start) * unit
+        }
+    }
+
+    pub trait Rng: RngCore {
+        fn gen<T>(&mut self) -> T
+        where
+            Standard: Distribution<T>,
+        {
+            Standard.sample(self)
+        }
+
+        fn gen_range<T, R: SampleRange<T>>(&mut self, range: R) -> T {
+            range.sample_single(self)
+        }
+    }
+    impl<R: RngCore + ?Sized> Rng for R {}
+}
+
+pub mod rngs {
+    pub mod mock {
+        use crate::RngCore;
+
+        /// Yields `initial`, `initial + increment`, ... wrapping around.
+        #[derive(Debug, Clone)]
+        pub struct StepRng {
+            v: u64,
+            a: u64,
+        }
+        impl StepRng {
+            pub fn new(initial: u64, increment: u64) -> Self {
+                Self {
+                    v: initial,
+                    a: increment,
+                }
+            }
+        }
+        impl RngCore for StepRng {
+            fn next_u32(&mut self) -> u32 {
+                self.next_u64() as u32
+            }
+            fn next_u64(&mut self) -> u64 {
+                let res = self.v;
+                self.v = self.v.wrapping_add(self.a);
+                res
+            }
+        }
+    }
+}
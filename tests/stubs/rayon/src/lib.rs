@@ -0,0 +1,113 @@
+//! Minimal stand-in for `rayon` 1.x, used by the tests of `implem` which must build offline.
+//!
+//! Parallel iterators are backed by vectors, `sum` splits them over two threads.
+
+pub mod prelude {
+    pub use crate::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
+}
+
+pub mod iter {
+    use std::iter::Sum;
+
+    pub trait ParallelIterator: Sized + Send {
+        type Item: Send;
+
+        #[doc(hidden)]
+        fn into_items(self) -> Vec<Self::Item>;
+
+        fn sum<S>(self) -> S
+        where
+            S: Send + Sum<Self::Item> + Sum<S>,
+        {
+            let mut left = self.into_items();
+            let right = left.split_off(left.len() / 2);
+            std::thread::scope(|scope| {
+                let right = scope.spawn(|| right.into_iter().sum::<S>());
+                let left = left.into_iter().sum::<S>();
+                vec![left, right.join().unwrap()].into_iter().sum()
+            })
+        }
+    }
+
+    pub trait IntoParallelIterator {
+        type Iter: ParallelIterator<Item = Self::Item>;
+        type Item: Send;
+
+        fn into_par_iter(self) -> Self::Iter;
+    }
+
+    impl<T: ParallelIterator> IntoParallelIterator for T {
+        type Iter = T;
+        type Item = T::Item;
+        fn into_par_iter(self) -> T {
+            self
+        }
+    }
+
+    pub trait IntoParallelRefIterator<'data> {
+        type Iter: ParallelIterator<Item = Self::Item>;
+        type Item: Send + 'data;
+
+        fn par_iter(&'data self) -> Self::Iter;
+    }
+
+    impl<'data, I: 'data + ?Sized> IntoParallelRefIterator<'data> for I
+    where
+        &'data I: IntoParallelIterator,
+    {
+        type Iter = <&'data I as IntoParallelIterator>::Iter;
+        type Item = <&'data I as IntoParallelIterator>::Item;
+        fn par_iter(&'data self) -> Self::Iter {
+            self.into_par_iter()
+        }
+    }
+}
+
+pub mod vec {
+    use crate::iter::{IntoParallelIterator, ParallelIterator};
+
+    pub struct IntoIter<T: Send> {
+        vec: Vec<T>,
+    }
+    impl<T: Send> ParallelIterator for IntoIter<T> {
+        type Item = T;
+        fn into_items(self) -> Vec<T> {
+            self.vec
+        }
+    }
+    impl<T: Send> IntoParallelIterator for Vec<T> {
+        type Iter = IntoIter<T>;
+        type Item = T;
+        fn into_par_iter(self) -> IntoIter<T> {
+            IntoIter { vec: self }
+        }
+    }
+    impl<'data, T: Sync + 'data> IntoParallelIterator for &'data Vec<T> {
+        type Iter = crate::slice::Iter<'data, T>;
+        type Item = &'data T;
+        fn into_par_iter(self) -> Self::Iter {
+            self.as_slice().into_par_iter()
+        }
+    }
+}
+
+pub mod slice {
+    use crate::iter::{IntoParallelIterator, ParallelIterator};
+
+    pub struct Iter<'data, T: Sync> {
+        slice: &'data [T],
+    }
+    impl<'data, T: Sync + 'data> ParallelIterator for Iter<'data, T> {
+        type Item = &'data T;
+        fn into_items(self) -> Vec<&'data T> {
+            self.slice.iter().collect()
+        }
+    }
+    impl<'data, T: Sync + 'data> IntoParallelIterator for &'data [T] {
+        type Iter = Iter<'data, T>;
+        type Item = &'data T;
+        fn into_par_iter(self) -> Self::Iter {
+            Iter { slice: self }
+        }
+    }
+}
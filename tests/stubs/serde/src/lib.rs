@@ -0,0 +1,328 @@
+//! Minimal stand-in for `serde` 1.x, used by the tests of `implem` which must build offline.
+//!
+//! Only covers what the tests need, but the items `implem` generates impls for have the exact
+//! signatures of the real ones.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+pub use de::{Deserialize, Deserializer};
+pub use ser::{Serialize, Serializer};
+
+pub mod ser {
+    use super::*;
+
+    pub trait Error: Sized + std::error::Error {
+        fn custom<T>(msg: T) -> Self
+        where
+            T: Display;
+    }
+
+    pub trait Serialize {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer;
+    }
+
+    pub trait SerializeMap {
+        type Ok;
+        type Error: Error;
+        fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error>;
+        fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error>;
+        fn end(self) -> Result<Self::Ok, Self::Error>;
+    }
+
+    pub trait Serializer: Sized {
+        type Ok;
+        type Error: Error;
+        type SerializeMap: SerializeMap<Ok = Self::Ok, Error = Self::Error>;
+
+        fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error>;
+        fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error>;
+        fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error>;
+        fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error>;
+        fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error>;
+        fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error>;
+
+        fn collect_str<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+        where
+            T: ?Sized + Display,
+        {
+            self.serialize_str(&value.to_string())
+        }
+    }
+
+    macro_rules! serialize_as {
+        ($($ty:ty => $method:ident as $as_ty:ty),* $(,)?) => {$(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.$method(*self as $as_ty)
+                }
+            }
+        )*};
+    }
+    serialize_as! {
+        bool => serialize_bool as bool,
+        u8 => serialize_u64 as u64,
+        u16 => serialize_u64 as u64,
+        u32 => serialize_u64 as u64,
+        u64 => serialize_u64 as u64,
+        usize => serialize_u64 as u64,
+        i8 => serialize_i64 as i64,
+        i16 => serialize_i64 as i64,
+        i32 => serialize_i64 as i64,
+        i64 => serialize_i64 as i64,
+        f32 => serialize_f64 as f64,
+        f64 => serialize_f64 as f64,
+    }
+
+    impl Serialize for str {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self)
+        }
+    }
+    impl Serialize for String {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self)
+        }
+    }
+    impl<'a> Serialize for std::borrow::Cow<'a, str> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self)
+        }
+    }
+    impl<'a, T: ?Sized + Serialize> Serialize for &'a T {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            (**self).serialize(serializer)
+        }
+    }
+    impl<K: Serialize, V: Serialize> Serialize for BTreeMap<K, V> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut map = serializer.serialize_map(Some(self.len()))?;
+            for (key, value) in self {
+                map.serialize_key(key)?;
+                map.serialize_value(value)?;
+            }
+            map.end()
+        }
+    }
+}
+
+pub mod de {
+    use super::*;
+
+    pub trait Error: Sized + std::error::Error {
+        fn custom<T>(msg: T) -> Self
+        where
+            T: Display;
+    }
+
+    pub trait Deserialize<'de>: Sized {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>;
+    }
+
+    pub trait MapAccess<'de> {
+        type Error: Error;
+        fn next_key<K: Deserialize<'de>>(&mut self) -> Result<Option<K>, Self::Error>;
+        fn next_value<V: Deserialize<'de>>(&mut self) -> Result<V, Self::Error>;
+    }
+
+    struct Expected<'a, V>(&'a V);
+    impl<'a, 'de, V: Visitor<'de>> Display for Expected<'a, V> {
+        fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            self.0.expecting(fmt)
+        }
+    }
+    fn invalid_type<'de, V: Visitor<'de>, E: Error>(visitor: &V, got: &str) -> E {
+        E::custom(format_args!(
+            "invalid type: {}, expected {}",
+            got,
+            Expected(visitor)
+        ))
+    }
+
+    pub trait Visitor<'de>: Sized {
+        type Value;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result;
+
+        fn visit_bool<E: Error>(self, _: bool) -> Result<Self::Value, E> {
+            Err(invalid_type(&self, "boolean"))
+        }
+        fn visit_i64<E: Error>(self, _: i64) -> Result<Self::Value, E> {
+            Err(invalid_type(&self, "integer"))
+        }
+        fn visit_u64<E: Error>(self, _: u64) -> Result<Self::Value, E> {
+            Err(invalid_type(&self, "integer"))
+        }
+        fn visit_f64<E: Error>(self, _: f64) -> Result<Self::Value, E> {
+            Err(invalid_type(&self, "floating point"))
+        }
+        fn visit_str<E: Error>(self, _: &str) -> Result<Self::Value, E> {
+            Err(invalid_type(&self, "string"))
+        }
+        fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<Self::Value, E> {
+            self.visit_str(v)
+        }
+        fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+            self.visit_str(&v)
+        }
+        fn visit_map<A: MapAccess<'de>>(self, _: A) -> Result<Self::Value, A::Error> {
+            Err(invalid_type(&self, "map"))
+        }
+    }
+
+    /// Unlike the real trait, the type-specific methods forward to `deserialize_any` by default.
+    pub trait Deserializer<'de>: Sized {
+        type Error: Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error>;
+
+        fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            self.deserialize_any(visitor)
+        }
+        fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            self.deserialize_any(visitor)
+        }
+        fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            self.deserialize_any(visitor)
+        }
+        fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            self.deserialize_any(visitor)
+        }
+        fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            self.deserialize_any(visitor)
+        }
+        fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            self.deserialize_any(visitor)
+        }
+        fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    struct PrimitiveVisitor<T>(&'static str, std::marker::PhantomData<T>);
+
+    macro_rules! deserialize_int {
+        ($($ty:ty),* $(,)?) => {$(
+            impl<'de> Visitor<'de> for PrimitiveVisitor<$ty> {
+                type Value = $ty;
+                fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                    fmt.write_str(self.0)
+                }
+                fn visit_u64<E: Error>(self, v: u64) -> Result<$ty, E> {
+                    std::convert::TryFrom::try_from(v).map_err(E::custom)
+                }
+                fn visit_i64<E: Error>(self, v: i64) -> Result<$ty, E> {
+                    std::convert::TryFrom::try_from(v).map_err(E::custom)
+                }
+            }
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_any(
+                        PrimitiveVisitor::<$ty>(stringify!($ty), std::marker::PhantomData),
+                    )
+                }
+            }
+        )*};
+    }
+    deserialize_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64);
+
+    impl<'de> Visitor<'de> for PrimitiveVisitor<f64> {
+        type Value = f64;
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str(self.0)
+        }
+        fn visit_u64<E: Error>(self, v: u64) -> Result<f64, E> {
+            Ok(v as f64)
+        }
+        fn visit_i64<E: Error>(self, v: i64) -> Result<f64, E> {
+            Ok(v as f64)
+        }
+        fn visit_f64<E: Error>(self, v: f64) -> Result<f64, E> {
+            Ok(v)
+        }
+    }
+    impl<'de> Deserialize<'de> for f64 {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_f64(PrimitiveVisitor::<f64>("f64", std::marker::PhantomData))
+        }
+    }
+
+    impl<'de> Visitor<'de> for PrimitiveVisitor<bool> {
+        type Value = bool;
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str(self.0)
+        }
+        fn visit_bool<E: Error>(self, v: bool) -> Result<bool, E> {
+            Ok(v)
+        }
+    }
+    impl<'de> Deserialize<'de> for bool {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_bool(PrimitiveVisitor::<bool>(
+                "a boolean",
+                std::marker::PhantomData,
+            ))
+        }
+    }
+
+    impl<'de> Visitor<'de> for PrimitiveVisitor<String> {
+        type Value = String;
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str(self.0)
+        }
+        fn visit_str<E: Error>(self, v: &str) -> Result<String, E> {
+            Ok(v.to_owned())
+        }
+        fn visit_string<E: Error>(self, v: String) -> Result<String, E> {
+            Ok(v)
+        }
+    }
+    impl<'de> Deserialize<'de> for String {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_string(PrimitiveVisitor::<String>(
+                "a string",
+                std::marker::PhantomData,
+            ))
+        }
+    }
+
+    struct BorrowedStrVisitor;
+    impl<'de> Visitor<'de> for BorrowedStrVisitor {
+        type Value = &'de str;
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str("a borrowed string")
+        }
+        fn visit_borrowed_str<E: Error>(self, v: &'de str) -> Result<&'de str, E> {
+            Ok(v)
+        }
+    }
+    impl<'de: 'a, 'a> Deserialize<'de> for &'a str {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(BorrowedStrVisitor)
+        }
+    }
+
+    struct MapVisitor<K, V>(std::marker::PhantomData<(K, V)>);
+    impl<'de, K: Ord + Deserialize<'de>, V: Deserialize<'de>> Visitor<'de> for MapVisitor<K, V> {
+        type Value = BTreeMap<K, V>;
+        fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+            fmt.write_str("a map")
+        }
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut res = BTreeMap::new();
+            while let Some(key) = map.next_key()? {
+                res.insert(key, map.next_value()?);
+            }
+            Ok(res)
+        }
+    }
+    impl<'de, K: Ord + Deserialize<'de>, V: Deserialize<'de>> Deserialize<'de> for BTreeMap<K, V> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_map(MapVisitor(std::marker::PhantomData))
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Minimal stand-in for `serde_json` 1.x, used by the tests of `implem` which must build offline.
+//!
+//! Handles booleans, numbers, strings and maps with string keys, which is all the tests need.
+
+use std::fmt;
+
+use serde::de::{self, Visitor};
+use serde::ser;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug)]
+pub struct Error(String);
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(&self.0)
+    }
+}
+impl std::error::Error for Error {}
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+pub fn to_string<T: ?Sized + Serialize>(value: &T) -> Result<String> {
+    value.serialize(Serializer)
+}
+
+pub fn from_str<'a, T: Deserialize<'a>>(s: &'a str) -> Result<T> {
+    let mut deserializer = Deserializer { input: s };
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.skip_ws();
+    if deserializer.input.is_empty() {
+        Ok(value)
+    } else {
+        Err(Error("trailing characters".into()))
+    }
+}
+
+struct Serializer;
+impl ser::Serializer for Serializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeMap = MapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_i64(self, v: i64) -> Result<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_u64(self, v: u64) -> Result<String> {
+        Ok(v.to_string())
+    }
+    fn serialize_f64(self, v: f64) -> Result<String> {
+        Ok(format!("{:?}", v))
+    }
+    fn serialize_str(self, v: &str) -> Result<String> {
+        let mut res = String::with_capacity(v.len() + 2);
+        res.push('"');
+        for c in v.chars() {
+            match c {
+                '"' => res.push_str("\\\""),
+                '\\' => res.push_str("\\\\"),
+                '\n' => res.push_str("\\n"),
+                c => res.push(c),
+            }
+        }
+        res.push('"');
+        Ok(res)
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<MapSerializer> {
+        Ok(MapSerializer { out: "{".into() })
+    }
+}
+
+struct MapSerializer {
+    out: String,
+}
+impl ser::SerializeMap for MapSerializer {
+    type Ok = String;
+    type Error = Error;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<()> {
+        let key = key.serialize(Serializer)?;
+        if !key.starts_with('"') {
+            return Err(Error("key must be a string".into()));
+        }
+        if self.out.len() > 1 {
+            self.out.push(',');
+        }
+        self.out.push_str(&key);
+        Ok(())
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<()> {
+        self.out.push(':');
+        self.out.push_str(&value.serialize(Serializer)?);
+        Ok(())
+    }
+    fn end(mut self) -> Result<String> {
+        self.out.push('}');
+        Ok(self.out)
+    }
+}
+
+enum Str<'de> {
+    Borrowed(&'de str),
+    Owned(String),
+}
+
+struct Deserializer<'de> {
+    input: &'de str,
+}
+impl<'de> Deserializer<'de> {
+    fn skip_ws(&mut self) {
+        self.input = self.input.trim_start();
+    }
+    fn eat(&mut self, c: char) -> Result<()> {
+        self.skip_ws();
+        if self.input.starts_with(c) {
+            self.input = &self.input[c.len_utf8()..];
+            Ok(())
+        } else {
+            Err(Error(format!("expected `{}`", c)))
+        }
+    }
+    fn parse_str(&mut self) -> Result<Str<'de>> {
+        self.eat('"')?;
+        let mut escaped = None;
+        let mut chars = self.input.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    let raw = &self.input[..i];
+                    self.input = &self.input[i + 1..];
+                    return Ok(escaped.map_or(Str::Borrowed(raw), Str::Owned));
+                }
+                '\\' => {
+                    let s = escaped.get_or_insert_with(|| self.input[..i].to_string());
+                    match chars.next() {
+                        Some((_, 'n')) => s.push('\n'),
+                        Some((_, c @ ('"' | '\\'))) => s.push(c),
+                        _ => return Err(Error("invalid escape".into())),
+                    }
+                }
+                c => {
+                    if let Some(s) = escaped.as_mut() {
+                        s.push(c)
+                    }
+                }
+            }
+        }
+        Err(Error("unterminated string".into()))
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.skip_ws();
+        match self.input.chars().next() {
+            Some('"') => match self.parse_str()? {
+                Str::Borrowed(s) => visitor.visit_borrowed_str(s),
+                Str::Owned(s) => visitor.visit_string(s),
+            },
+            Some('{') => {
+                self.eat('{')?;
+                visitor.visit_map(MapAccess {
+                    de: self,
+                    first: true,
+                })
+            }
+            Some('t') if self.input.starts_with("true") => {
+                self.input = &self.input[4..];
+                visitor.visit_bool(true)
+            }
+            Some('f') if self.input.starts_with("false") => {
+                self.input = &self.input[5..];
+                visitor.visit_bool(false)
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let len = self
+                    .input
+                    .find(|c: char| !(c.is_ascii_digit() || "+-.eE".contains(c)))
+                    .unwrap_or(self.input.len());
+                let (num, rest) = self.input.split_at(len);
+                self.input = rest;
+                let invalid = || Error(format!("invalid number `{}`", num));
+                if num.contains(|c| ".eE".contains(c)) {
+                    visitor.visit_f64(num.parse().map_err(|_| invalid())?)
+                } else if num.starts_with('-') {
+                    visitor.visit_i64(num.parse().map_err(|_| invalid())?)
+                } else {
+                    visitor.visit_u64(num.parse().map_err(|_| invalid())?)
+                }
+            }
+            _ => Err(Error("expected value".into())),
+        }
+    }
+}
+
+struct MapAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    first: bool,
+}
+impl<'a, 'de> de::MapAccess<'de> for MapAccess<'a, 'de> {
+    type Error = Error;
+    fn next_key<K: Deserialize<'de>>(&mut self) -> Result<Option<K>> {
+        self.de.skip_ws();
+        if self.de.input.starts_with('}') {
+            self.de.input = &self.de.input[1..];
+            return Ok(None);
+        }
+        if !std::mem::replace(&mut self.first, false) {
+            self.de.eat(',')?;
+        }
+        K::deserialize(&mut *self.de).map(Some)
+    }
+    fn next_value<V: Deserialize<'de>>(&mut self) -> Result<V> {
+        self.de.eat(':')?;
+        V::deserialize(&mut *self.de)
+    }
+}
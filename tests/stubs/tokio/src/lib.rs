@@ -0,0 +1,207 @@
+//! Minimal stand-in for `tokio` 1.x, used by the tests of `implem` which must build offline.
+//!
+//! Only provides the IO traits, `io::copy` and a current-thread runtime busy-polling its future.
+
+pub mod io {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    pub use std::io::{Error, ErrorKind, Result};
+
+    /// Unlike the real one, only wraps initialized memory.
+    pub struct ReadBuf<'a> {
+        buf: &'a mut [u8],
+        filled: usize,
+    }
+    impl<'a> ReadBuf<'a> {
+        pub fn new(buf: &'a mut [u8]) -> Self {
+            Self { buf, filled: 0 }
+        }
+        pub fn capacity(&self) -> usize {
+            self.buf.len()
+        }
+        pub fn filled(&self) -> &[u8] {
+            &self.buf[..self.filled]
+        }
+        pub fn remaining(&self) -> usize {
+            self.capacity() - self.filled
+        }
+        pub fn initialize_unfilled(&mut self) -> &mut [u8] {
+            &mut self.buf[self.filled..]
+        }
+        pub fn advance(&mut self, n: usize) {
+            assert!(n <= self.remaining(), "filled overflow");
+            self.filled += n;
+        }
+        pub fn put_slice(&mut self, buf: &[u8]) {
+            self.initialize_unfilled()[..buf.len()].copy_from_slice(buf);
+            self.advance(buf.len());
+        }
+        pub fn clear(&mut self) {
+            self.filled = 0;
+        }
+    }
+
+    pub trait AsyncRead {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<Result<()>>;
+    }
+
+    pub trait AsyncWrite {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<Result<usize>>;
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>>;
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>>;
+
+        fn is_write_vectored(&self) -> bool {
+            false
+        }
+    }
+
+    impl AsyncRead for &[u8] {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<Result<()>> {
+            let len = std::cmp::min(self.len(), buf.remaining());
+            let (head, tail) = self.split_at(len);
+            buf.put_slice(head);
+            *self = tail;
+            Poll::Ready(Ok(()))
+        }
+    }
+    impl<T: ?Sized + AsyncRead + Unpin> AsyncRead for &mut T {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &mut ReadBuf<'_>,
+        ) -> Poll<Result<()>> {
+            Pin::new(&mut **self).poll_read(cx, buf)
+        }
+    }
+
+    impl AsyncWrite for Vec<u8> {
+        fn poll_write(
+            self: Pin<&mut Self>,
+            _: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<Result<usize>> {
+            self.get_mut().extend_from_slice(buf);
+            Poll::Ready(Ok(buf.len()))
+        }
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<()>> {
+            Poll::Ready(Ok(()))
+        }
+    }
+    impl<T: ?Sized + AsyncWrite + Unpin> AsyncWrite for &mut T {
+        fn poll_write(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            buf: &[u8],
+        ) -> Poll<Result<usize>> {
+            Pin::new(&mut **self).poll_write(cx, buf)
+        }
+        fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+            Pin::new(&mut **self).poll_flush(cx)
+        }
+        fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+            Pin::new(&mut **self).poll_shutdown(cx)
+        }
+    }
+
+    pub trait AsyncWriteExt: AsyncWrite {
+        fn shutdown(&mut self) -> Shutdown<'_, Self>
+        where
+            Self: Unpin,
+        {
+            Shutdown { writer: self }
+        }
+    }
+    impl<W: ?Sized + AsyncWrite> AsyncWriteExt for W {}
+
+    pub struct Shutdown<'a, W: ?Sized> {
+        writer: &'a mut W,
+    }
+    impl<W: ?Sized + AsyncWrite + Unpin> Future for Shutdown<'_, W> {
+        type Output = Result<()>;
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+            Pin::new(&mut *self.writer).poll_shutdown(cx)
+        }
+    }
+
+    /// Copies with a small buffer so that tests go through several reads and writes.
+    pub async fn copy<'a, R, W>(reader: &'a mut R, writer: &'a mut W) -> Result<u64>
+    where
+        R: AsyncRead + Unpin + ?Sized,
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let mut total = 0;
+        let mut bytes = [0; 4];
+        loop {
+            let mut buf = ReadBuf::new(&mut bytes);
+            std::future::poll_fn(|cx| Pin::new(&mut *reader).poll_read(cx, &mut buf)).await?;
+            let mut filled = buf.filled();
+            if filled.is_empty() {
+                std::future::poll_fn(|cx| Pin::new(&mut *writer).poll_flush(cx)).await?;
+                return Ok(total);
+            }
+            while !filled.is_empty() {
+                let n = std::future::poll_fn(|cx| Pin::new(&mut *writer).poll_write(cx, filled))
+                    .await?;
+                if n == 0 {
+                    return Err(ErrorKind::WriteZero.into());
+                }
+                filled = &filled[n..];
+                total += n as u64;
+            }
+        }
+    }
+}
+
+pub mod runtime {
+    use std::future::Future;
+    use std::task::{Context, Poll, Waker};
+
+    pub struct Builder {
+        _priv: (),
+    }
+    impl Builder {
+        pub fn new_current_thread() -> Self {
+            Self { _priv: () }
+        }
+        pub fn enable_all(&mut self) -> &mut Self {
+            self
+        }
+        pub fn build(&mut self) -> std::io::Result<Runtime> {
+            Ok(Runtime { _priv: () })
+        }
+    }
+
+    pub struct Runtime {
+        _priv: (),
+    }
+    impl Runtime {
+        /// Busy-polls `future` with a no-op waker.
+        pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+            let mut future = Box::pin(future);
+            let mut cx = Context::from_waker(Waker::noop());
+            loop {
+                if let Poll::Ready(res) = future.as_mut().poll(&mut cx) {
+                    return res;
+                }
+                std::thread::yield_now();
+            }
+        }
+    }
+}
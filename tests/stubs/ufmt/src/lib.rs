@@ -0,0 +1,132 @@
+//! Minimal stand-in for `ufmt` 0.2, used by the tests of `implem` which must build offline.
+//!
+//! `uwrite!` only supports `{}` placeholders, `uDebug` impls are called through a `Formatter`.
+
+#![allow(non_camel_case_types)]
+
+pub trait uWrite {
+    type Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error>;
+
+    fn write_char(&mut self, c: char) -> Result<(), Self::Error> {
+        self.write_str(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+pub struct Formatter<'w, W: uWrite + ?Sized> {
+    writer: &'w mut W,
+}
+impl<'w, W: uWrite + ?Sized> Formatter<'w, W> {
+    pub fn new(writer: &'w mut W) -> Self {
+        Self { writer }
+    }
+    pub fn write_str(&mut self, s: &str) -> Result<(), W::Error> {
+        self.writer.write_str(s)
+    }
+    pub fn write_char(&mut self, c: char) -> Result<(), W::Error> {
+        self.writer.write_char(c)
+    }
+}
+
+pub trait uDisplay {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized;
+}
+
+pub trait uDebug {
+    fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+    where
+        W: uWrite + ?Sized;
+}
+
+impl uDisplay for u32 {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        let mut buf = [0; 10];
+        let mut n = *self;
+        let mut start = buf.len();
+        loop {
+            start -= 1;
+            buf[start] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        f.write_str(std::str::from_utf8(&buf[start..]).unwrap())
+    }
+}
+impl uDebug for u32 {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uDisplay::fmt(self, f)
+    }
+}
+impl uDisplay for str {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_str(self)
+    }
+}
+impl uDebug for str {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.write_char('"')?;
+        f.write_str(self)?;
+        f.write_char('"')
+    }
+}
+impl<T: uDisplay + ?Sized> uDisplay for &T {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uDisplay::fmt(&**self, f)
+    }
+}
+impl<T: uDebug + ?Sized> uDebug for &T {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uDebug::fmt(&**self, f)
+    }
+}
+
+#[doc(hidden)]
+pub trait UnstableDoAsFormatter {
+    type Writer: uWrite + ?Sized;
+
+    fn do_as_formatter(
+        &mut self,
+        f: impl FnOnce(&mut Formatter<'_, Self::Writer>) -> Result<(), <Self::Writer as uWrite>::Error>,
+    ) -> Result<(), <Self::Writer as uWrite>::Error>;
+}
+impl<W: uWrite + ?Sized> UnstableDoAsFormatter for W {
+    type Writer = W;
+    fn do_as_formatter(
+        &mut self,
+        f: impl FnOnce(&mut Formatter<'_, W>) -> Result<(), W::Error>,
+    ) -> Result<(), W::Error> {
+        f(&mut Formatter::new(self))
+    }
+}
+impl<W: uWrite + ?Sized> UnstableDoAsFormatter for Formatter<'_, W> {
+    type Writer = W;
+    fn do_as_formatter(
+        &mut self,
+        f: impl FnOnce(&mut Formatter<'_, W>) -> Result<(), W::Error>,
+    ) -> Result<(), W::Error> {
+        f(self)
+    }
+}
+
+/// Writes to a `uWrite` or a `Formatter`, only supports `{}` placeholders.
+#[macro_export]
+macro_rules! uwrite {
+    ($w:expr, $fmt:literal $(, $arg:expr)* $(,)?) => {{
+        use $crate::UnstableDoAsFormatter as _;
+        $w.do_as_formatter(|f| {
+            let mut pieces = $fmt.split("{}");
+            f.write_str(pieces.next().unwrap())?;
+            $(
+                $crate::uDisplay::fmt(&$arg, f)?;
+                f.write_str(pieces.next().expect("more arguments than placeholders"))?;
+            )*
+            assert!(pieces.next().is_none(), "more placeholders than arguments");
+            Ok(())
+        })
+    }};
+}
@@ -0,0 +1,33 @@
+//! Minimal stand-in for `zeroize` 1.x, used by the tests of `implem` which must build offline.
+//!
+//! Uses plain writes, which unlike the real crate's volatile ones may be optimized away.
+
+pub trait Zeroize {
+    fn zeroize(&mut self);
+}
+
+/// Marker for types zeroizing themselves on drop.
+pub trait ZeroizeOnDrop {}
+
+impl Zeroize for u8 {
+    fn zeroize(&mut self) {
+        *self = 0
+    }
+}
+impl Zeroize for [u8] {
+    fn zeroize(&mut self) {
+        self.iter_mut().for_each(Zeroize::zeroize)
+    }
+}
+impl<const N: usize> Zeroize for [u8; N] {
+    fn zeroize(&mut self) {
+        self[..].zeroize()
+    }
+}
+/// Zeroizes the elements, then clears the vector.
+impl Zeroize for Vec<u8> {
+    fn zeroize(&mut self) {
+        self[..].zeroize();
+        self.clear()
+    }
+}
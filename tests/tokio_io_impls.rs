@@ -0,0 +1,90 @@
+//! Tests the `tokio-io` definitions, run with `cargo test --features tokio-io`.
+
+#![cfg(feature = "tokio-io")]
+
+use std::pin::Pin;
+use std::task::Poll;
+
+use implem::implem;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+
+/// Counts the bytes read, pending every other poll.
+pub struct MeteredReader<R> {
+    inner: R,
+    read: usize,
+    ready: bool,
+}
+implem! {
+    impl(R) for MeteredReader<R> where (R: AsyncRead + Unpin) {
+        AsyncRead {
+            |self: Pin<&mut Self>, cx, buf| {
+                let this = self.get_mut();
+                this.ready = !this.ready;
+                if !this.ready {
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+                let before = buf.filled().len();
+                let res = Pin::new(&mut this.inner).poll_read(cx, buf);
+                this.read += buf.filled().len() - before;
+                res
+            }
+        }
+    }
+}
+
+/// Writes at most three bytes at a time, counting flushes and shutdowns.
+pub struct SlowWriter<W> {
+    inner: W,
+    flushes: usize,
+    shutdowns: usize,
+}
+implem! {
+    impl(W) for SlowWriter<W> where (W: AsyncWrite + Unpin) {
+        AsyncWrite {
+            poll_write: |self: Pin<&mut Self>, cx, buf| {
+                let len = buf.len().min(3);
+                Pin::new(&mut self.get_mut().inner).poll_write(cx, &buf[..len])
+            },
+            poll_shutdown: |self: Pin<&mut Self>, cx| {
+                let this = self.get_mut();
+                this.shutdowns += 1;
+                Pin::new(&mut this.inner).poll_shutdown(cx)
+            },
+            poll_flush: |self: Pin<&mut Self>, cx| {
+                let this = self.get_mut();
+                this.flushes += 1;
+                Pin::new(&mut this.inner).poll_flush(cx)
+            },
+        }
+    }
+}
+
+#[test]
+fn copy() {
+    let mut reader = MeteredReader {
+        inner: &b"hello world"[..],
+        read: 0,
+        ready: false,
+    };
+    let mut writer = SlowWriter {
+        inner: Vec::new(),
+        flushes: 0,
+        shutdowns: 0,
+    };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let copied = runtime
+        .block_on(async {
+            let copied = tokio::io::copy(&mut reader, &mut writer).await?;
+            writer.shutdown().await?;
+            Ok::<_, std::io::Error>(copied)
+        })
+        .unwrap();
+    assert_eq!(copied, 11);
+    assert_eq!(reader.read, 11);
+    assert_eq!(writer.inner, b"hello world");
+    assert_eq!(writer.flushes, 1);
+    assert_eq!(writer.shutdowns, 1);
+}
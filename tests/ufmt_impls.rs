@@ -0,0 +1,90 @@
+//! Tests the `ufmt` definitions, run with `cargo test --features ufmt`.
+
+#![cfg(feature = "ufmt")]
+
+use implem::implem;
+use ufmt::{uDebug, uWrite, uwrite, Formatter};
+
+/// Fixed-capacity buffer, as found on targets without an allocator.
+pub struct Buf {
+    data: [u8; 16],
+    len: usize,
+}
+impl Buf {
+    fn new() -> Self {
+        Self {
+            data: [0; 16],
+            len: 0,
+        }
+    }
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.data[..self.len]).unwrap()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Overflow;
+impl uWrite for Buf {
+    type Error = Overflow;
+    fn write_str(&mut self, s: &str) -> Result<(), Overflow> {
+        let end = self.len + s.len();
+        self.data
+            .get_mut(self.len..end)
+            .ok_or(Overflow)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+pub struct Volts(u32);
+implem! {
+    for Volts {
+        Display {
+            |&self, fmt| write!(fmt, "{}V", self.0)
+        }
+        uDisplay {
+            |&self, fmt| uwrite!(fmt, "{}V", self.0)
+        }
+        uDebug {
+            |&self, fmt| uwrite!(fmt, "Volts({})", self.0)
+        }
+    }
+}
+
+pub struct Pair<T>(T, T);
+implem! {
+    impl(T) for Pair<T> where (T: ufmt::uDisplay) {
+        uDisplay {
+            |&self, fmt| uwrite!(fmt, "({}, {})", self.0, self.1)
+        }
+    }
+}
+
+#[test]
+fn udisplay() {
+    let mut buf = Buf::new();
+    uwrite!(buf, "{}", Volts(5)).unwrap();
+    assert_eq!(buf.as_str(), Volts(5).to_string());
+
+    let mut buf = Buf::new();
+    uwrite!(buf, "{} {}", Pair(1, 2), Pair(Volts(3), Volts(4))).unwrap();
+    assert_eq!(buf.as_str(), "(1, 2) (3V, 4V)");
+}
+
+#[test]
+fn udebug() {
+    let mut buf = Buf::new();
+    uDebug::fmt(&Volts(230), &mut Formatter::new(&mut buf)).unwrap();
+    assert_eq!(buf.as_str(), "Volts(230)");
+}
+
+#[test]
+fn error() {
+    let mut buf = Buf::new();
+    assert_eq!(
+        uwrite!(buf, "{} {}", Volts(1234567), Volts(1234567)),
+        Err(Overflow)
+    );
+    assert_eq!(buf.as_str(), "1234567V 1234567");
+}
@@ -0,0 +1,78 @@
+//! Tests the `zeroize` definitions, run with `cargo test --features zeroize`.
+
+#![cfg(feature = "zeroize")]
+
+use implem::implem;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+pub struct Secret {
+    bytes: Vec<u8>,
+}
+implem! {
+    for Secret {
+        Zeroize {
+            |&mut self| self.bytes.zeroize()
+        }
+    }
+}
+
+/// Wipes the borrowed key when dropped, so that the test can observe it.
+pub struct Guard<'a> {
+    key: &'a mut [u8],
+}
+implem! {
+    impl('a) for Guard<'a> {
+        Zeroize {
+            |&mut self| self.key.zeroize()
+        }
+        ZeroizeOnDrop {}
+    }
+}
+
+/// The where clause matches the type's own bounds, as required by the `Drop` impl.
+pub struct Wiped<'a, T: Zeroize + ?Sized> {
+    inner: &'a mut T,
+}
+implem! {
+    impl('a, T) for Wiped<'a, T> where (T: Zeroize + ?Sized) {
+        Zeroize {
+            |&mut self| self.inner.zeroize()
+        }
+        ZeroizeOnDrop {}
+    }
+}
+
+fn assert_zeroize_on_drop<T: ZeroizeOnDrop + ?Sized>() {}
+
+#[test]
+fn zeroize() {
+    let mut secret = Secret {
+        bytes: vec![1, 2, 3],
+    };
+    secret.zeroize();
+    assert!(secret.bytes.is_empty());
+}
+
+#[test]
+fn zeroize_on_drop() {
+    assert_zeroize_on_drop::<Guard>();
+    assert_zeroize_on_drop::<Wiped<[u8]>>();
+
+    let mut key = [7; 4];
+    let guard = Guard { key: &mut key };
+    assert_eq!(guard.key, [7; 4]);
+    drop(guard);
+    assert_eq!(key, [0; 4]);
+
+    let mut key = [7; 4];
+    {
+        let _wiped = Wiped {
+            inner: &mut key[1..],
+        };
+    }
+    assert_eq!(key, [7, 0, 0, 0]);
+
+    let mut key = vec![7; 4];
+    drop(Wiped { inner: &mut key });
+    assert!(key.is_empty());
+}